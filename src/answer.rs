@@ -0,0 +1,188 @@
+//! Puzzle answers as JSON, one object per line from each day's `main`
+//!
+//! `{"puzzle":"22b","answer":2089,"extras":{"sequence":[-2,1,-1,3]}}`
+//! Extras keep the order they were added in.
+
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+    List(Vec<Value>),
+}
+
+macro_rules! integer_value {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(value: $t) -> Self {
+                Value::Integer(value.into())
+            }
+        })*
+    };
+}
+integer_value!(i8, i32, i64, u8, u32, u64);
+
+// usize has no lossless `Into<i128>`, but it never has more than 64 bits
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Integer(value as i128)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>, const N: usize> From<[T; N]> for Value {
+    fn from(value: [T; N]) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Quoted with JSON escapes
+fn write_string(f: &mut Formatter<'_>, s: &str) -> Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Value::Integer(value) => write!(f, "{value}"),
+            Value::Text(value) => write_string(f, value),
+            Value::List(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    puzzle: &'static str,
+    value: Value,
+    extras: Vec<(&'static str, Value)>,
+}
+
+impl Answer {
+    /// Answer to a puzzle named like `"22b"`
+    pub fn new(puzzle: &'static str, value: impl Into<Value>) -> Answer {
+        Answer {
+            puzzle,
+            value: value.into(),
+            extras: Vec::new(),
+        }
+    }
+    /// Add a named intermediate result, replacing one with the same name
+    pub fn with(mut self, name: &'static str, value: impl Into<Value>) -> Answer {
+        let value = value.into();
+        match self.extras.iter_mut().find(|(n, _)| *n == name) {
+            Some(extra) => extra.1 = value,
+            None => self.extras.push((name, value)),
+        }
+        self
+    }
+    pub fn puzzle(&self) -> &str {
+        self.puzzle
+    }
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+    pub fn extra(&self, name: &str) -> Option<&Value> {
+        self.extras
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{{\"puzzle\":")?;
+        write_string(f, self.puzzle)?;
+        write!(f, ",\"answer\":{},\"extras\":{{", self.value)?;
+        for (i, (name, value)) in self.extras.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write_string(f, name)?;
+            write!(f, ":{value}")?;
+        }
+        write!(f, "}}}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        let answer = Answer::new("22b", 2089usize).with("sequence", [-2i8, 1, -1, 3]);
+        assert_eq!(
+            answer.to_string(),
+            r#"{"puzzle":"22b","answer":2089,"extras":{"sequence":[-2,1,-1,3]}}"#
+        );
+        assert_eq!(answer.value(), &Value::Integer(2089));
+        assert_eq!(
+            answer.extra("sequence"),
+            Some(&Value::from(vec![-2i8, 1, -1, 3]))
+        );
+        assert_eq!(answer.extra("score"), None);
+        assert_eq!(
+            Answer::new("01a", 1u64).to_string(),
+            r#"{"puzzle":"01a","answer":1,"extras":{}}"#
+        );
+    }
+    #[test]
+    fn test_extras() {
+        let answer = Answer::new("18b", "20,44")
+            .with("byte", 2900usize)
+            .with("position", [20usize, 44])
+            .with("byte", 2901usize);
+        assert_eq!(
+            answer.to_string(),
+            r#"{"puzzle":"18b","answer":"20,44","extras":{"byte":2901,"position":[20,44]}}"#
+        );
+    }
+    #[test]
+    fn test_escape() {
+        let value = Value::from("a\"b\\c\nd\u{1}é");
+        assert_eq!(value.to_string(), r#""a\"b\\c\nd\u0001é""#);
+        assert_eq!(Value::from(Vec::<u8>::new()).to_string(), "[]");
+        assert_eq!(Value::from(u64::MAX).to_string(), "18446744073709551615");
+    }
+}
@@ -0,0 +1,187 @@
+//! Antennas and their antinodes for day 8
+//!
+//! The step between two antennas is divided by the GCD of its components,
+//! so walking along it visits every grid point on the line through them.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+/// Which points on the line through two antennas are antinodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Points where one antenna is exactly twice as far away as the other
+    Doubled,
+    /// Every point on the line
+    AllHarmonics,
+    /// Points on the line no more than this many antenna spacings beyond
+    /// the nearest antenna, 0 only gives the points between them
+    MaxHarmonic(usize),
+}
+
+#[derive(Debug)]
+pub struct Antennas {
+    pub shape: [usize; 2],
+    pub frequencies: BTreeMap<char, Vec<[usize; 2]>>,
+}
+
+impl FromStr for Antennas {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nrows = s.lines().count();
+        let ncols = s.lines().next().ok_or(())?.chars().count();
+        let shape = [nrows, ncols];
+        let mut frequencies: BTreeMap<char, Vec<[usize; 2]>> = BTreeMap::new();
+        for (irow, line) in s.lines().enumerate() {
+            if line.chars().count() != ncols {
+                return Err(());
+            }
+            for (icol, c) in line.chars().enumerate() {
+                if c == '.' || c == '#' {
+                    continue;
+                }
+                frequencies.entry(c).or_default().push([irow, icol]);
+            }
+        }
+        Ok(Antennas { shape, frequencies })
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Antennas {
+    fn in_bounds(&self, position: [i64; 2]) -> Option<[usize; 2]> {
+        let row = usize::try_from(position[0]).ok()?;
+        let col = usize::try_from(position[1]).ok()?;
+        (row < self.shape[0] && col < self.shape[1]).then_some([row, col])
+    }
+    /// Antinodes of one pair of antennas, added to `out`
+    fn pair_antinodes(
+        &self,
+        a: [usize; 2],
+        b: [usize; 2],
+        rule: Rule,
+        out: &mut BTreeSet<[usize; 2]>,
+    ) {
+        let a = a.map(|x| x as i64);
+        let b = b.map(|x| x as i64);
+        let diff = [b[0] - a[0], b[1] - a[1]];
+        let g = gcd(diff[0], diff[1]);
+        let step = [diff[0] / g, diff[1] / g];
+        // points are a + t * step, with b at t == g
+        let at = |t: i64| [a[0] + step[0] * t, a[1] + step[1] * t];
+        let ts: Vec<i64> = match rule {
+            Rule::Doubled => {
+                let mut ts = vec![-g, 2 * g];
+                if g % 3 == 0 {
+                    ts.extend([g / 3, 2 * g / 3]);
+                }
+                ts
+            }
+            Rule::AllHarmonics | Rule::MaxHarmonic(_) => {
+                let limit = match rule {
+                    Rule::MaxHarmonic(n) => n as i64 * g,
+                    _ => i64::MAX,
+                };
+                // walk out from each antenna until leaving the grid
+                let mut ts = (0..=g).collect::<Vec<_>>();
+                for (from, direction) in [(0, -1), (g, 1)] {
+                    let mut distance = 1;
+                    while distance <= limit
+                        && self.in_bounds(at(from + direction * distance)).is_some()
+                    {
+                        ts.push(from + direction * distance);
+                        distance += 1;
+                    }
+                }
+                ts
+            }
+        };
+        out.extend(ts.into_iter().filter_map(|t| self.in_bounds(at(t))));
+    }
+    /// Antinodes made by each frequency's antennas
+    pub fn antinodes(&self, rule: Rule) -> BTreeMap<char, BTreeSet<[usize; 2]>> {
+        self.frequencies
+            .iter()
+            .map(|(&frequency, positions)| {
+                let mut out = BTreeSet::new();
+                for (i, &a) in positions.iter().enumerate() {
+                    for &b in &positions[i + 1..] {
+                        self.pair_antinodes(a, b, rule, &mut out);
+                    }
+                }
+                (frequency, out)
+            })
+            .collect()
+    }
+    /// Antinodes of any frequency
+    pub fn all_antinodes(&self, rule: Rule) -> BTreeSet<[usize; 2]> {
+        self.antinodes(rule).into_values().flatten().collect()
+    }
+    /// The map with `#` at antinodes, antennas are drawn on top
+    pub fn render(&self, antinodes: &BTreeSet<[usize; 2]>) -> String {
+        let mut grid = vec![vec!['.'; self.shape[1]]; self.shape[0]];
+        for &[row, col] in antinodes {
+            grid[row][col] = '#';
+        }
+        for (&frequency, positions) in &self.frequencies {
+            for &[row, col] in positions {
+                grid[row][col] = frequency;
+            }
+        }
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doubled() {
+        let antennas = include_str!("bin/08_test.txt").parse::<Antennas>().unwrap();
+        assert_eq!(antennas.all_antinodes(Rule::Doubled).len(), 14);
+        let per_frequency = antennas.antinodes(Rule::Doubled);
+        assert_eq!(per_frequency[&'A'].len(), 5);
+        // the two antennas are 3 apart, so the points a third of the way
+        // along are antinodes too
+        let antennas = "a..a".parse::<Antennas>().unwrap();
+        let antinodes = antennas.all_antinodes(Rule::Doubled);
+        assert_eq!(antennas.render(&antinodes), "a##a\n");
+    }
+    #[test]
+    fn test_harmonics() {
+        let antennas = include_str!("bin/08_test.txt").parse::<Antennas>().unwrap();
+        assert_eq!(antennas.all_antinodes(Rule::AllHarmonics).len(), 34);
+        // the step between these two antennas is reduced to 1 column
+        let antennas = "..a.a...".parse::<Antennas>().unwrap();
+        let antinodes = antennas.all_antinodes(Rule::AllHarmonics);
+        assert_eq!(antennas.render(&antinodes), "##a#a###\n");
+        let antinodes = antennas.all_antinodes(Rule::MaxHarmonic(1));
+        assert_eq!(antennas.render(&antinodes), "##a#a##.\n");
+        let antinodes = antennas.all_antinodes(Rule::MaxHarmonic(0));
+        assert_eq!(antennas.render(&antinodes), "..a#a...\n");
+    }
+    #[test]
+    fn test_render() {
+        let antennas = "T.........\n...T......\n.T........\n..........\n..........\n..........\n..........\n..........\n..........\n.........."
+            .parse::<Antennas>()
+            .unwrap();
+        let antinodes = antennas.all_antinodes(Rule::AllHarmonics);
+        assert_eq!(antinodes.len(), 9);
+        assert_eq!(
+            antennas.render(&antinodes),
+            "T....#....\n...T......\n.T....#...\n.........#\n..#.......\n..........\n...#......\n..........\n....#.....\n..........\n"
+        );
+    }
+}
@@ -1,28 +1,61 @@
-fn extract_lists(str: &str) -> [Vec<u32>; 2] {
-    let count = str.lines().count();
-    let mut vecs = [Vec::with_capacity(count), Vec::with_capacity(count)];
-    for line in str.lines() {
-        let (a, b) = line.split_once(" ").unwrap();
-        let a: u32 = a.parse().unwrap();
-        let b: u32 = b.trim().parse().unwrap();
-        vecs[0].push(a);
-        vecs[1].push(b);
+use aoc24::location_lists::{self, Lists};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
+
+#[derive(Debug)]
+struct Puzzle {
+    lists: Lists,
+}
+
+impl FromStr for Puzzle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lists = s.parse::<Lists>()?;
+        if lists.columns() != 2 {
+            return Err(());
+        }
+        Ok(Puzzle { lists })
     }
-    vecs
 }
 
-fn process_lists(mut vecs: [Vec<u32>; 2]) -> u32 {
-    vecs[0].sort();
-    vecs[1].sort();
-    let mut diff = 0;
-    for (a, b) in vecs[0].iter().zip(vecs[1].iter()) {
-        diff += a.abs_diff(*b);
+impl Puzzle {
+    /// None if the answer doesn't fit in usize
+    fn process(&self) -> Option<usize> {
+        location_lists::distance(self.lists.column(0), self.lists.column(1))
     }
-    diff
 }
+
 fn main() {
-    let test = include_str!("01.txt");
-    let test = extract_lists(test);
-    let test = process_lists(test);
-    println!("hello: {test:?}");
+    logging::init();
+    let puzzle = include_str!("01.txt").parse::<Puzzle>().unwrap();
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("01a", out));
+    assert_eq!(out, 2164381);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc24::fuzz;
+
+    #[test]
+    fn test() {
+        let puzzle = include_str!("01_test.txt").parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), Some(11));
+    }
+    #[test]
+    fn test_fuzz() {
+        let max = "18446744073709551615";
+        let large = [
+            format!("{max} {max}\n{max} {max}\n"),
+            format!("0 {max}\n0 {max}\n"),
+        ];
+        let seeds = [include_str!("01_test.txt"), &large[0], &large[1]];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
@@ -1,29 +1,68 @@
-use counter::Counter;
+use aoc24::location_lists::{self, Lists};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
-fn extract_lists(str: &str) -> [Vec<usize>; 2] {
-    let count = str.lines().count();
-    let mut vecs = [Vec::with_capacity(count), Vec::with_capacity(count)];
-    for line in str.lines() {
-        let (a, b) = line.split_once(" ").unwrap();
-        let a: usize = a.parse().unwrap();
-        let b: usize = b.trim().parse().unwrap();
-        vecs[0].push(a);
-        vecs[1].push(b);
+#[derive(Debug)]
+struct Puzzle {
+    lists: Lists,
+}
+
+impl FromStr for Puzzle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lists = s.parse::<Lists>()?;
+        if lists.columns() != 2 {
+            return Err(());
+        }
+        Ok(Puzzle { lists })
     }
-    vecs
 }
 
-fn process_lists(vecs: [Vec<usize>; 2]) -> usize {
-    let counter = vecs[1].iter().collect::<Counter<_>>();
-    let mut out = 0;
-    for a in vecs[0].iter() {
-        out += a * counter[a];
+impl Puzzle {
+    /// None if the answer doesn't fit in usize
+    fn process(&self) -> Option<usize> {
+        location_lists::similarity(self.lists.column(0), self.lists.column(1))
     }
-    out
 }
+
 fn main() {
-    let test = include_str!("01.txt");
-    let test = extract_lists(test);
-    let test = process_lists(test);
-    println!("hello: {test:?}");
+    logging::init();
+    let puzzle = include_str!("01.txt").parse::<Puzzle>().unwrap();
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("01b", out));
+    assert_eq!(out, 20719933);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
+    #[test]
+    fn test() {
+        let puzzle = include_str!("01_test.txt").parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), Some(31));
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day01(&mut Rng::new(1), 100, 0.2);
+        let puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        let max = "18446744073709551615";
+        let large = [
+            format!("{max} {max}\n{max} {max}\n"),
+            format!("0 {max}\n0 {max}\n"),
+        ];
+        let seeds = [include_str!("01_test.txt"), &large[0], &large[1]];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
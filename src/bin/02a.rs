@@ -1,51 +1,51 @@
-fn extract(str: &str) -> Vec<Vec<usize>> {
+use aoc24::reports::Analyzer;
+use aoc24::{answer::Answer, logging};
+
+/// None if a level isn't a number
+fn extract(str: &str) -> Option<Vec<Vec<usize>>> {
     let count = str.lines().count();
     let mut vecs = Vec::with_capacity(count);
     for line in str.lines() {
         let count = line.split_whitespace().count();
         let mut vec = Vec::with_capacity(count);
         for val in line.split_whitespace() {
-            let val: usize = val.parse().unwrap();
+            let val: usize = val.parse().ok()?;
             vec.push(val);
         }
         vecs.push(vec);
     }
-    vecs
+    Some(vecs)
 }
 
-fn process_line(a: &[usize], increasing: bool) -> bool {
-    for chunk in a.windows(2) {
-        let d = if increasing {
-            chunk[1] as i64 - chunk[0] as i64
-        } else {
-            chunk[0] as i64 - chunk[1] as i64
-        };
-        let ok = matches!(d, 1..=3);
-        if !ok {
-            return false;
-        }
-    }
-    true
-}
 fn process(vecs: Vec<Vec<usize>>) -> usize {
-    let mut out = 0;
-    for a in vecs.iter() {
-        let ok = process_line(a, true) || process_line(a, false);
-        if ok {
-            out += 1;
-        }
-    }
-    out
+    let analyzer = Analyzer::new(1..=3, 0);
+    vecs.iter().filter(|a| analyzer.safe(a)).count()
 }
 fn main() {
+    logging::init();
     let out = include_str!("02_test.txt");
-    let out = extract(out);
+    let out = extract(out).unwrap();
     let out = process(out);
     assert_eq!(out, 2);
 
     let out = include_str!("02.txt");
-    let out = extract(out);
+    let out = extract(out).unwrap();
     let out = process(out);
     assert_eq!(out, 356);
-    println!("{out:?}");
+    println!("{}", Answer::new("02a", out));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc24::fuzz;
+
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("02_test.txt")], 300, |s| {
+            if let Some(vecs) = extract(s) {
+                process(vecs);
+            }
+        });
+    }
 }
@@ -1,57 +1,52 @@
-fn extract(str: &str) -> Vec<Vec<usize>> {
+use aoc24::reports::Analyzer;
+use aoc24::{answer::Answer, logging};
+
+/// None if a level isn't a number
+fn extract(str: &str) -> Option<Vec<Vec<usize>>> {
     let count = str.lines().count();
     let mut vecs = Vec::with_capacity(count);
     for line in str.lines() {
         let count = line.split_whitespace().count();
         let mut vec = Vec::with_capacity(count);
         for val in line.split_whitespace() {
-            let val: usize = val.parse().unwrap();
+            let val: usize = val.parse().ok()?;
             vec.push(val);
         }
         vecs.push(vec);
     }
-    vecs
+    Some(vecs)
 }
 
-fn process_line(a: &[usize], increasing: bool) -> bool {
-    for chunk in a.windows(2) {
-        let d = if increasing {
-            chunk[1] as i64 - chunk[0] as i64
-        } else {
-            chunk[0] as i64 - chunk[1] as i64
-        };
-        let ok = matches!(d, 1..=3);
-        if !ok {
-            return false;
-        }
-    }
-    true
-}
-fn process_line_at(a: &[usize], loc: usize) -> bool {
-    let a: Vec<usize> = a
-        .iter()
-        .enumerate()
-        .filter(|(i, _x)| i != &loc)
-        .map(|(_i, x)| *x)
-        .collect();
-    process_line(&a, true) || process_line(&a, false)
-}
 fn process(vecs: Vec<Vec<usize>>) -> usize {
-    let mut out = 0;
-    for a in vecs.iter() {
-        for i in 0..a.len() {
-            let ok = process_line_at(a, i);
-            if ok {
-                out += 1;
-                break;
-            }
-        }
-    }
-    out
+    let analyzer = Analyzer::new(1..=3, 1);
+    vecs.iter().filter(|a| analyzer.safe(a)).count()
 }
 fn main() {
+    logging::init();
     let out = include_str!("02.txt");
-    let out = extract(out);
+    let out = extract(out).unwrap();
     let out = process(out);
-    println!("{out:?}");
+    println!("{}", Answer::new("02b", out));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
+    #[test]
+    fn test_generated() {
+        let input = generate::day02(&mut Rng::new(2), 50);
+        let out = process(extract(&input).unwrap());
+        assert!(out <= 50);
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("02_test.txt")], 300, |s| {
+            if let Some(vecs) = extract(s) {
+                process(vecs);
+            }
+        });
+    }
 }
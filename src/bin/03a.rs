@@ -1,15 +1,16 @@
-use regex::Regex;
+use aoc24::instructions::{Instruction, Scanner};
+use aoc24::{answer::Answer, logging};
 #[derive(Debug)]
 struct Mul {
     a: usize,
     b: usize,
 }
 fn extract(str: &str) -> Vec<Mul> {
-    let re = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
+    let scanner = Scanner::new(vec![Instruction::call("mul", 2, 3)]);
     let mut out = Vec::new();
-    for (_, [a, b]) in re.captures_iter(str).map(|c| c.extract()) {
-        let a = a.parse().unwrap();
-        let b = b.parse().unwrap();
+    for token in scanner.scan(str) {
+        let a = token.args[0] as usize;
+        let b = token.args[1] as usize;
         out.push(Mul { a, b });
     }
     out
@@ -23,6 +24,7 @@ fn process(vecs: Vec<Mul>) -> usize {
     out
 }
 fn main() {
+    logging::init();
     // let out = include_str!("03_test.txt");
     // let out = extract(out);
     // let out = process(out);
@@ -32,5 +34,5 @@ fn main() {
     let out = extract(out);
     let out = process(out);
     assert!(out > 1552138);
-    println!("{out:?}");
+    println!("{}", Answer::new("03a", out));
 }
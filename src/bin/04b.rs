@@ -1,96 +1,22 @@
+use aoc24::word_search::{find_stencils, parse_grid, Stencil};
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Token {
-    X,
-    M,
-    A,
-    S,
-    O,
-}
-
-fn extract(str: &str) -> Array2<Token> {
-    let nrows = str.lines().count();
-    let ncols = str.lines().next().unwrap().chars().count();
-    let shape = (nrows, ncols);
-    let mut tokens = Vec::with_capacity(nrows * ncols);
-    for line in str.lines() {
-        for char in line.chars() {
-            match char {
-                'X' => tokens.push(Token::X),
-                'M' => tokens.push(Token::M),
-                'A' => tokens.push(Token::A),
-                'S' => tokens.push(Token::S),
-                _ => tokens.push(Token::O),
-            }
-        }
-    }
-    Array2::from_shape_vec(shape, tokens).unwrap()
-}
-
-fn check_location(tokens: ArrayView2<Token>, i: usize, j: usize) -> bool {
-    let ul = tokens[[i - 1, j - 1]];
-    let ur = tokens[[i - 1, j + 1]];
-    let ll = tokens[[i + 1, j - 1]];
-    let lr = tokens[[i + 1, j + 1]];
-    match ul {
-        Token::M => {
-            if lr != Token::S {
-                return false;
-            }
-        }
-        Token::S => {
-            if lr != Token::M {
-                return false;
-            }
-        }
-        _ => {
-            return false;
-        }
-    }
-    match ur {
-        Token::M => {
-            if ll != Token::S {
-                return false;
-            }
-        }
-        Token::S => {
-            if ll != Token::M {
-                return false;
-            }
-        }
-        _ => {
-            return false;
-        }
-    }
-    true
-}
-
-fn process(tokens: ArrayView2<Token>) -> usize {
-    let mut out = 0;
-
-    let shape = tokens.shape();
-    for ((i, j), token) in tokens.indexed_iter() {
-        if i == 0 || j == 0 || i == shape[0] - 1 || j == shape[1] - 1 {
-            continue;
-        }
-        if token == &Token::A && check_location(tokens, i, j) {
-            out += 1;
-        }
-    }
-    out
+fn process(grid: ArrayView2<char>) -> usize {
+    let cross = "M.S\n.A.\nM.S".parse::<Stencil>().unwrap();
+    find_stencils(grid, &[cross]).len()
 }
 
 fn main() {
+    logging::init();
     let out = include_str!("04_test.txt");
-    let out = extract(out);
-    // dbg!(&out);
+    let out = parse_grid(out).unwrap();
     let out = process(out.view());
     assert_eq!(out, 9);
 
     let out = include_str!("04.txt");
-    let out = extract(out);
+    let out = parse_grid(out).unwrap();
     let out = process(out.view());
     assert_eq!(out, 1815);
-    println!("{out}");
+    println!("{}", Answer::new("04b", out));
 }
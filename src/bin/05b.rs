@@ -1,99 +1,7 @@
-use std::{
-    collections::{btree_map::Entry, BTreeMap, BTreeSet},
-    str::FromStr,
-};
+use aoc24::page_order::{Rule, Ruleset, Update};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
-#[derive(Debug)]
-struct Rule {
-    left: usize,
-    right: usize,
-}
-
-impl FromStr for Rule {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split = s.split_once("|");
-        if split.is_none() {
-            return Err(());
-        }
-        let (left, right) = split.unwrap();
-        let left = left.parse::<usize>();
-        if left.is_err() {
-            return Err(());
-        }
-        let left = left.unwrap();
-        let right = right.parse::<usize>();
-        if right.is_err() {
-            return Err(());
-        }
-        let right = right.unwrap();
-        Ok(Rule { left, right })
-    }
-}
-#[derive(Debug, Clone)]
-struct Update {
-    pages: Vec<usize>,
-}
-impl FromStr for Update {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let pages: Vec<&str> = s.split(",").collect();
-        if pages.is_empty() {
-            return Err(());
-        }
-        let pages = pages.iter().map(|p| p.parse::<usize>()).collect::<Vec<_>>();
-        if pages.iter().any(|p| p.is_err()) {
-            return Err(());
-        }
-        let pages: Vec<usize> = pages.iter().map(|p| *p.as_ref().unwrap()).collect();
-        Ok(Update { pages })
-    }
-}
-impl Update {
-    fn check(&self, ruleset: &Ruleset) -> bool {
-        let mut seen = BTreeSet::new();
-        for page in &self.pages {
-            if let Some(rights) = ruleset.rules.get(page) {
-                if seen.intersection(rights).count() > 0 {
-                    return false;
-                }
-            }
-            seen.insert(*page);
-        }
-        true
-    }
-    fn middle(&self) -> usize {
-        self.pages[(self.pages.len() - 1) / 2]
-    }
-    fn reorder(&self, ruleset: &Ruleset) -> Update {
-        let mut out = self.clone();
-        loop {
-            let mut seen = BTreeSet::new();
-            let mut locs = BTreeMap::new();
-            let mut swap = None;
-            for (ipage, page) in out.pages.iter().enumerate() {
-                if let Some(rights) = ruleset.rules.get(page) {
-                    if let Some(violation) = seen.intersection(rights).next() {
-                        swap = Some([ipage, locs[violation]]);
-                    }
-                }
-                if swap.is_some() {
-                    break;
-                }
-                seen.insert(*page);
-                locs.insert(page, ipage);
-            }
-            if let Some([a, b]) = swap {
-                out.pages.swap(a, b);
-            } else {
-                break;
-            }
-        }
-        out
-    }
-}
 #[derive(Debug)]
 struct Puzzle {
     rules: Vec<Rule>,
@@ -122,74 +30,57 @@ impl FromStr for Puzzle {
     }
 }
 
-#[derive(Debug)]
-struct Ruleset {
-    rules: BTreeMap<usize, BTreeSet<usize>>,
-}
-impl Ruleset {
-    fn new(rules: &[Rule]) -> Self {
-        let mut ruleset: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
-        for Rule { left, right } in rules {
-            match ruleset.entry(*left) {
-                Entry::Vacant(e) => {
-                    e.insert(BTreeSet::from([*right]));
-                }
-                Entry::Occupied(mut e) => {
-                    e.get_mut().insert(*right);
-                }
-            }
-        }
-        Ruleset { rules: ruleset }
-    }
-}
-
 impl Puzzle {
-    fn process(&self) -> usize {
-        let mut out = 0;
+    /// None if the rules can't put an update in order
+    fn process(&self) -> Option<usize> {
+        let mut out = 0usize;
         let ruleset = Ruleset::new(&self.rules);
         for update in &self.updates {
-            if !update.check(&ruleset) {
-                out += update.reorder(&ruleset).middle();
+            if update.check(&ruleset).is_err() {
+                out = out.checked_add(update.reorder(&ruleset).ok()?.middle())?;
             }
         }
-        out
+        Some(out)
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("05_test.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
+    let out = puzzle.process().unwrap();
     assert_eq!(out, 123);
 
     let puzzle = include_str!("05.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
+    let out = puzzle.process().unwrap();
     assert_eq!(out, 6305);
-    println!("{out}");
+    println!("{}", Answer::new("05b", out));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
 
     #[test]
-    fn test_check_fail() {
-        let rules = vec![Rule { left: 1, right: 2 }];
-        let ruleset = Ruleset::new(&rules);
-        let update = Update { pages: vec![2, 1] };
-        assert!(!update.check(&ruleset));
+    fn test_cycle() {
+        let puzzle = "1|2\n2|3\n3|1\n\n1,2\n".parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), Some(0));
+        let puzzle = "1|2\n2|3\n3|1\n\n3,2,1\n".parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), None);
     }
     #[test]
-    fn test_check_pass() {
-        let rules = vec![Rule { left: 1, right: 2 }];
-        let ruleset = Ruleset::new(&rules);
-        let update = Update { pages: vec![1, 2] };
-        assert!(update.check(&ruleset));
+    fn test_generated() {
+        let input = generate::day05(&mut Rng::new(5), 11, 20);
+        let puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().is_some());
     }
     #[test]
-    fn test_check_fail2() {
-        let rules = vec![Rule { left: 1, right: 2 }, Rule { left: 1, right: 3 }];
-        let ruleset = Ruleset::new(&rules);
-        let update = Update { pages: vec![3, 1] };
-        assert!(!update.check(&ruleset));
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("05_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
     }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, grid, logging};
 use ndarray::prelude::*;
 use std::{collections::BTreeSet, str::FromStr};
 
@@ -62,31 +63,20 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let mut tokens = Vec::with_capacity(nrows * ncols);
-        let mut position = [0, 0];
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, char) in line.chars().enumerate() {
-                let token = match char {
-                    '.' => Some(Token::Clear),
-                    '#' => Some(Token::Blocked),
-                    '^' => {
-                        position = [irow, icol];
-                        Some(Token::Clear)
-                    }
-                    _ => None,
-                };
-                if token.is_none() {
-                    return Err(());
-                }
-                tokens.push(token.unwrap());
+        let mut position = None;
+        let map = grid::parse(s, |pos, c| match c {
+            '.' => Some(Token::Clear),
+            '#' => Some(Token::Blocked),
+            '^' if position.is_none() => {
+                position = Some(pos);
+                Some(Token::Clear)
             }
-        }
-        let map = Array2::<Token>::from_shape_vec((nrows, ncols), tokens).unwrap();
+            _ => None,
+        })
+        .ok_or(())?;
         Ok(Puzzle {
             map,
-            position,
+            position: position.ok_or(())?,
             direction: Direction::Up,
         })
     }
@@ -95,6 +85,7 @@ impl FromStr for Puzzle {
 impl Puzzle {
     fn process(&self) -> usize {
         let mut positions = BTreeSet::new();
+        let mut states = BTreeSet::new();
         let mut position = self.position;
         let mut direction = self.direction;
         positions.insert(position);
@@ -102,6 +93,10 @@ impl Puzzle {
             position = new_position;
             direction = new_direction;
             positions.insert(position);
+            // stop if the guard is walking in a loop
+            if !states.insert((position, direction)) {
+                break;
+            }
         }
         positions.len()
     }
@@ -133,15 +128,17 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("06.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
     assert_eq!(out, 5534);
-    println!("{out}");
+    println!("{}", Answer::new("06a", out));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
 
     #[test]
     fn test() {
@@ -149,4 +146,13 @@ mod tests {
         let out = puzzle.process();
         assert_eq!(out, 41);
     }
+    #[test]
+    fn test_fuzz() {
+        assert!("..\n..^\n.".parse::<Puzzle>().is_err());
+        fuzz::fuzz(0, &[include_str!("06_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
@@ -1,5 +1,10 @@
+use aoc24::parallel;
+use aoc24::{answer::Answer, grid, logging};
 use ndarray::prelude::*;
-use std::{collections::BTreeSet, str::FromStr};
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
+};
 
 #[derive(Debug)]
 enum Token {
@@ -7,7 +12,7 @@ enum Token {
     Blocked,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Copy, Clone)]
+#[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Copy, Clone, Hash)]
 enum Direction {
     Left,
     Right,
@@ -58,97 +63,192 @@ fn update_position(
     }
     Some(new_position)
 }
+/// Number of steps from `from` to `to` walking in `direction`
+/// Returns None if `to` is not on the ray starting at `from`
+fn ray_distance(from: [usize; 2], to: [usize; 2], direction: Direction) -> Option<usize> {
+    match direction {
+        Direction::Up if from[1] == to[1] && to[0] <= from[0] => Some(from[0] - to[0]),
+        Direction::Down if from[1] == to[1] && to[0] >= from[0] => Some(to[0] - from[0]),
+        Direction::Left if from[0] == to[0] && to[1] <= from[1] => Some(from[1] - to[1]),
+        Direction::Right if from[0] == to[0] && to[1] >= from[1] => Some(to[1] - from[1]),
+        _ => None,
+    }
+}
+fn advance(position: [usize; 2], direction: Direction, steps: usize) -> [usize; 2] {
+    match direction {
+        Direction::Left => [position[0], position[1] - steps],
+        Direction::Right => [position[0], position[1] + steps],
+        Direction::Up => [position[0] - steps, position[1]],
+        Direction::Down => [position[0] + steps, position[1]],
+    }
+}
+
+/// For every cell and direction, the last clear cell before the next obstacle
+/// None if the guard would walk off the map instead
+#[derive(Debug)]
+struct JumpTable {
+    stops: Array3<Option<[usize; 2]>>,
+}
+
+impl JumpTable {
+    fn new(map: &Array2<Token>) -> JumpTable {
+        let shape = map.shape();
+        let mut stops = Array3::from_elem([shape[0], shape[1], 4], None);
+        for direction in [
+            Direction::Left,
+            Direction::Right,
+            Direction::Up,
+            Direction::Down,
+        ] {
+            // sweep so the neighbour in `direction` is always filled in first
+            let rows = (0..shape[0]).collect::<Vec<_>>();
+            let cols = (0..shape[1]).collect::<Vec<_>>();
+            let rows = if direction == Direction::Down {
+                rows.into_iter().rev().collect()
+            } else {
+                rows
+            };
+            let cols = if direction == Direction::Right {
+                cols.into_iter().rev().collect()
+            } else {
+                cols
+            };
+            let index = direction_index(&direction);
+            for &irow in rows.iter() {
+                for &icol in cols.iter() {
+                    let position = [irow, icol];
+                    let stop = match update_position(position, direction, shape) {
+                        None => None,
+                        Some(next) => match map[next] {
+                            Token::Blocked => Some(position),
+                            Token::Clear => stops[[next[0], next[1], index]],
+                        },
+                    };
+                    stops[[irow, icol, index]] = stop;
+                }
+            }
+        }
+        JumpTable { stops }
+    }
+    /// Walk from `position` in `direction` until the next obstacle
+    /// `block` is an extra obstruction laid over the table
+    fn jump(
+        &self,
+        position: [usize; 2],
+        direction: Direction,
+        block: [usize; 2],
+    ) -> Option<[usize; 2]> {
+        let stop = self.stops[[position[0], position[1], direction_index(&direction)]];
+        let Some(block_distance) = ray_distance(position, block, direction) else {
+            return stop;
+        };
+        let blocked = match stop {
+            None => true,
+            Some(stop) => block_distance <= ray_distance(position, stop, direction).unwrap(),
+        };
+        if blocked {
+            Some(advance(position, direction, block_distance - 1))
+        } else {
+            stop
+        }
+    }
+}
+
+/// Loop created by adding an obstruction at `block`
+#[derive(Debug, PartialEq)]
+struct LoopInfo {
+    block: [usize; 2],
+    /// Number of steps to walk the loop once
+    length: usize,
+    /// First turning point on the loop that the guard reaches
+    entry: [usize; 2],
+    entry_direction: Direction,
+}
 
 #[derive(Debug)]
 struct Puzzle {
     map: Array2<Token>,
     position: [usize; 2],
     direction: Direction,
-    position_direction: Array3<bool>,
 }
 
 impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let mut tokens = Vec::with_capacity(nrows * ncols);
-        let mut position = [0, 0];
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, char) in line.chars().enumerate() {
-                let token = match char {
-                    '.' => Some(Token::Clear),
-                    '#' => Some(Token::Blocked),
-                    '^' => {
-                        position = [irow, icol];
-                        Some(Token::Clear)
-                    }
-                    _ => None,
-                };
-                if token.is_none() {
-                    return Err(());
-                }
-                tokens.push(token.unwrap());
+        let mut position = None;
+        let map = grid::parse(s, |pos, c| match c {
+            '.' => Some(Token::Clear),
+            '#' => Some(Token::Blocked),
+            '^' if position.is_none() => {
+                position = Some(pos);
+                Some(Token::Clear)
             }
-        }
-        let map = Array2::<Token>::from_shape_vec((nrows, ncols), tokens).unwrap();
-        let position_direction = Array3::from_elem([nrows, ncols, 4], false);
+            _ => None,
+        })
+        .ok_or(())?;
         Ok(Puzzle {
             map,
-            position,
+            position: position.ok_or(())?,
             direction: Direction::Up,
-            position_direction,
         })
     }
 }
 
 impl Puzzle {
-    fn check_if_block_makes_loop(&mut self, block_location: [usize; 2]) -> bool {
-        match self.map[block_location] {
-            Token::Blocked => {
-                panic!();
-            }
-            Token::Clear => {
-                self.map[block_location] = Token::Blocked;
-            }
+    /// Follow the guard turn by turn with `block` added to the map
+    /// Returns the loop if the guard never leaves the map
+    fn find_loop(&self, table: &JumpTable, block: [usize; 2]) -> Option<LoopInfo> {
+        if matches!(self.map[block], Token::Blocked) {
+            panic!();
         }
-        let mut looping = false;
-        self.position_direction.iter_mut().for_each(|x| {
-            *x = false;
-        });
+        let mut turns = Vec::new();
+        let mut seen = HashMap::new();
         let mut position = self.position;
         let mut direction = self.direction;
-        self.position_direction[[position[0], position[1], direction_index(&direction)]] = true;
-        while let Some((new_position, new_direction)) = self.update(position, direction) {
-            position = new_position;
-            direction = new_direction;
-            let pd_index = [position[0], position[1], direction_index(&direction)];
-            if self.position_direction[pd_index] {
-                looping = true;
-                break;
+        loop {
+            if let Some(&start) = seen.get(&(position, direction)) {
+                let length = (start..turns.len())
+                    .map(|i| {
+                        let (from, _): ([usize; 2], Direction) = turns[i];
+                        let to = turns.get(i + 1).map_or(position, |t| t.0);
+                        from[0].abs_diff(to[0]) + from[1].abs_diff(to[1])
+                    })
+                    .sum();
+                let (entry, entry_direction) = turns[start];
+                return Some(LoopInfo {
+                    block,
+                    length,
+                    entry,
+                    entry_direction,
+                });
             }
-            self.position_direction[pd_index] = true;
+            seen.insert((position, direction), turns.len());
+            turns.push((position, direction));
+            position = table.jump(position, direction, block)?;
+            direction = rotate_right(&direction);
         }
-        self.map[block_location] = Token::Clear;
-        looping
     }
-    fn process(&mut self) -> usize {
-        let positions = self.find_original_locations();
-        let mut out = 0;
-        for position in positions {
+    /// Every obstruction on the original path that traps the guard in a loop
+    fn find_loops(&self) -> Vec<LoopInfo> {
+        let table = JumpTable::new(&self.map);
+        let candidates = self
+            .find_original_locations()
+            .into_iter()
             // not allowed to use initial location
-            if position == self.position {
-                continue;
-            }
-            if self.check_if_block_makes_loop(position) {
-                out += 1;
-            }
-        }
-        out
+            .filter(|&position| position != self.position)
+            .collect::<Vec<_>>();
+        parallel::map(&candidates, |&position| self.find_loop(&table, position))
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+    fn process(&self) -> usize {
+        self.find_loops().len()
     }
     fn find_original_locations(&self) -> BTreeSet<[usize; 2]> {
         let mut positions = BTreeSet::new();
+        let mut states = BTreeSet::new();
         let mut position = self.position;
         let mut direction = self.direction;
         positions.insert(position);
@@ -156,13 +256,17 @@ impl Puzzle {
             position = new_position;
             direction = new_direction;
             positions.insert(position);
+            // stop if the guard is walking in a loop
+            if !states.insert((position, direction)) {
+                break;
+            }
         }
         positions
     }
     /// Return new position if still on Map
     /// If guard can walk forward, that's what it will do
     /// Otherwise turn right and walk forward
-    /// If guard walks off map return None
+    /// If guard walks off map, or is boxed in, return None
     fn update(
         &self,
         position: [usize; 2],
@@ -173,7 +277,12 @@ impl Puzzle {
         let mut new_position = update_position(position, direction, shape)?;
         let mut new_direction = direction;
         // turn right if we need to
+        let mut turns = 0;
         while !matches!(self.map[new_position], Token::Clear) {
+            turns += 1;
+            if turns == 4 {
+                return None;
+            }
             new_direction = rotate_right(&new_direction);
             new_position = update_position(position, new_direction, shape)?;
         }
@@ -182,19 +291,22 @@ impl Puzzle {
 }
 
 fn main() {
-    let mut puzzle = include_str!("06.txt").parse::<Puzzle>().unwrap();
+    logging::init();
+    let puzzle = include_str!("06.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("06b", out));
     assert_eq!(out, 2262);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
 
     #[test]
     fn test() {
-        let mut puzzle = include_str!("06_test.txt").parse::<Puzzle>().unwrap();
+        let puzzle = include_str!("06_test.txt").parse::<Puzzle>().unwrap();
         let out = puzzle.process();
         assert_eq!(out, 6);
     }
@@ -204,4 +316,46 @@ mod tests {
         let out = puzzle.find_original_locations().len();
         assert_eq!(out, 41);
     }
+    #[test]
+    fn test_loops() {
+        let puzzle = include_str!("06_test.txt").parse::<Puzzle>().unwrap();
+        let blocks = puzzle
+            .find_loops()
+            .iter()
+            .map(|l| l.block)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, [[6, 3], [7, 6], [7, 7], [8, 1], [8, 3], [9, 7]]);
+    }
+    #[test]
+    fn test_loop_info() {
+        let puzzle = ".#...\n....#\n.....\n.....\n.^.#."
+            .parse::<Puzzle>()
+            .unwrap();
+        let table = JumpTable::new(&puzzle.map);
+        let out = puzzle.find_loop(&table, [3, 0]).unwrap();
+        assert_eq!(
+            out,
+            LoopInfo {
+                block: [3, 0],
+                length: 8,
+                entry: [1, 1],
+                entry_direction: Direction::Right,
+            }
+        );
+        assert!(puzzle.find_loop(&table, [2, 2]).is_none());
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day06(&mut Rng::new(6), [10, 12], 0.1);
+        input.parse::<Puzzle>().unwrap().process();
+    }
+    #[test]
+    fn test_fuzz() {
+        assert!("..\n..^\n.".parse::<Puzzle>().is_err());
+        fuzz::fuzz(0, &[include_str!("06_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
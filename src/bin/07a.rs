@@ -1,63 +1,7 @@
+use aoc24::equation::{Add, Equation, Multiply, Solver};
+use aoc24::{answer::Answer, logging};
 use std::str::FromStr;
 
-#[derive(Debug)]
-struct Equation {
-    result: usize,
-    values: Vec<usize>,
-}
-
-impl FromStr for Equation {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (result, values) = s.split_once(": ").unwrap();
-        let result = result.parse().unwrap();
-        let values: Vec<usize> = values
-            .split(" ")
-            .map(|x| x.parse::<usize>().unwrap())
-            .collect();
-        Ok(Equation { result, values })
-    }
-}
-
-impl Equation {
-    fn solvable(&self) -> bool {
-        self.count_solutions(0, self.values[0]) > 0
-    }
-    fn count_solutions(&self, position: usize, partial_value: usize) -> usize {
-        assert!(position < self.values.len() - 1);
-        let at_bottom = position == self.values.len() - 2;
-        let mut solutions = 0;
-        // try Add
-        {
-            let partial_value = partial_value + self.values[position + 1];
-            if partial_value <= self.result {
-                if at_bottom {
-                    if partial_value == self.result {
-                        solutions += 1;
-                    }
-                } else {
-                    solutions += self.count_solutions(position + 1, partial_value)
-                }
-            }
-        }
-        // try Multiply
-        {
-            let partial_value = partial_value * self.values[position + 1];
-            if partial_value <= self.result {
-                if at_bottom {
-                    if partial_value == self.result {
-                        solutions += 1;
-                    }
-                } else {
-                    solutions += self.count_solutions(position + 1, partial_value)
-                }
-            }
-        }
-        solutions
-    }
-}
-
 #[derive(Debug)]
 struct Puzzle {
     equations: Vec<Equation>,
@@ -70,40 +14,44 @@ impl FromStr for Puzzle {
         let count = s.lines().count();
         let mut equations = Vec::with_capacity(count);
         for line in s.lines() {
-            equations.push(line.parse::<Equation>().unwrap());
+            equations.push(line.parse::<Equation>()?);
         }
         Ok(Puzzle { equations })
     }
 }
 
 impl Puzzle {
-    fn process(&mut self) -> usize {
-        let mut out = 0;
+    /// Sum of the solvable results, None if it doesn't fit
+    fn process(&mut self) -> Option<usize> {
+        let solver = Solver::new(vec![Box::new(Add), Box::new(Multiply)]);
+        let mut out = 0usize;
         for equation in &self.equations {
-            if equation.solvable() {
-                out += equation.result;
+            if solver.solvable(equation) {
+                out = out.checked_add(equation.result)?;
             }
         }
-        out
+        Some(out)
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("07.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("07a", out));
     assert_eq!(out, 1038838357795);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
 
     #[test]
     fn test() {
         let mut puzzle = include_str!("07_test.txt").parse::<Puzzle>().unwrap();
         let out = puzzle.process();
-        assert_eq!(out, 3749);
+        assert_eq!(out, Some(3749));
     }
     #[test]
     fn test_no_zeros() {
@@ -115,4 +63,20 @@ mod tests {
             assert_ne!(equation.result, 0);
         }
     }
+    #[test]
+    fn test_overflow() {
+        let s = "18446744073709551615: 18446744073709551615\n1: 1\n";
+        let mut puzzle = s.parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), None);
+        let mut puzzle = s.lines().next().unwrap().parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), Some(usize::MAX));
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("07_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
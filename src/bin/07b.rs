@@ -1,84 +1,8 @@
+use aoc24::equation::{Add, Concatenate, Equation, Multiply, Solver};
+use aoc24::parallel;
+use aoc24::{answer::Answer, logging};
 use std::str::FromStr;
 
-#[derive(Debug)]
-struct Equation {
-    result: usize,
-    values: Vec<usize>,
-}
-
-impl FromStr for Equation {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (result, values) = s.split_once(": ").unwrap();
-        let result = result.parse().unwrap();
-        let values: Vec<usize> = values
-            .split(" ")
-            .map(|x| x.parse::<usize>().unwrap())
-            .collect();
-        Ok(Equation { result, values })
-    }
-}
-
-fn concatenate(a: usize, b: usize) -> usize {
-    let mut value = 1;
-    while value <= b {
-        value *= 10;
-    }
-    a * value + b
-}
-
-impl Equation {
-    fn solvable(&self) -> bool {
-        self.count_solutions(0, self.values[0]) > 0
-    }
-    fn count_solutions(&self, position: usize, partial_value: usize) -> usize {
-        assert!(position < self.values.len() - 1);
-        let at_bottom = position == self.values.len() - 2;
-        let mut solutions = 0;
-        // try Add
-        {
-            let partial_value = partial_value + self.values[position + 1];
-            if partial_value <= self.result {
-                if at_bottom {
-                    if partial_value == self.result {
-                        solutions += 1;
-                    }
-                } else {
-                    solutions += self.count_solutions(position + 1, partial_value)
-                }
-            }
-        }
-        // try Multiply
-        {
-            let partial_value = partial_value * self.values[position + 1];
-            if partial_value <= self.result {
-                if at_bottom {
-                    if partial_value == self.result {
-                        solutions += 1;
-                    }
-                } else {
-                    solutions += self.count_solutions(position + 1, partial_value)
-                }
-            }
-        }
-        // try Concatenation
-        {
-            let partial_value = concatenate(partial_value, self.values[position + 1]);
-            if partial_value <= self.result {
-                if at_bottom {
-                    if partial_value == self.result {
-                        solutions += 1;
-                    }
-                } else {
-                    solutions += self.count_solutions(position + 1, partial_value)
-                }
-            }
-        }
-        solutions
-    }
-}
-
 #[derive(Debug)]
 struct Puzzle {
     equations: Vec<Equation>,
@@ -91,40 +15,51 @@ impl FromStr for Puzzle {
         let count = s.lines().count();
         let mut equations = Vec::with_capacity(count);
         for line in s.lines() {
-            equations.push(line.parse::<Equation>().unwrap());
+            equations.push(line.parse::<Equation>()?);
         }
         Ok(Puzzle { equations })
     }
 }
 
 impl Puzzle {
-    fn process(&mut self) -> usize {
-        let mut out = 0;
-        for equation in &self.equations {
-            if equation.solvable() {
-                out += equation.result;
+    /// Sum of the solvable results, None if it doesn't fit
+    fn process(&mut self) -> Option<usize> {
+        let solver = Solver::new(vec![
+            Box::new(Add),
+            Box::new(Multiply),
+            Box::new(Concatenate),
+        ]);
+        parallel::map(&self.equations, |equation| {
+            if solver.solvable(equation) {
+                equation.result
+            } else {
+                0
             }
-        }
-        out
+        })
+        .into_iter()
+        .try_fold(0usize, usize::checked_add)
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("07.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("07b", out));
     assert_eq!(out, 254136560217241);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
 
     #[test]
     fn test() {
         let mut puzzle = include_str!("07_test.txt").parse::<Puzzle>().unwrap();
         let out = puzzle.process();
-        assert_eq!(out, 11387);
+        assert_eq!(out, Some(11387));
     }
     #[test]
     fn test_no_zeros() {
@@ -136,4 +71,27 @@ mod tests {
             assert_ne!(equation.result, 0);
         }
     }
+    #[test]
+    fn test_overflow() {
+        let s = "18446744073709551615: 18446744073709551615\n1: 1\n";
+        let mut puzzle = s.parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), None);
+        let mut puzzle = s.lines().next().unwrap().parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process(), Some(usize::MAX));
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day07(&mut Rng::new(7), 20, 6);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        let seeds = [include_str!("07_test.txt"), "10: 5 10000000000000000000\n"];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
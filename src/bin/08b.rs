@@ -1,84 +1,41 @@
-use std::{
-    collections::{btree_map::Entry, BTreeMap, BTreeSet},
-    str::FromStr,
-};
+use aoc24::antennas::{Antennas, Rule};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Puzzle {
-    shape: [usize; 2],
-    antennas: BTreeMap<char, Vec<[usize; 2]>>,
+    antennas: Antennas,
 }
 
 impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let shape = [nrows, ncols];
-        let mut antennas = BTreeMap::new();
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, c) in line.chars().enumerate() {
-                if c == '.' {
-                    continue;
-                }
-                match antennas.entry(c) {
-                    Entry::Vacant(vacant_entry) => {
-                        vacant_entry.insert(vec![[irow, icol]]);
-                    }
-                    Entry::Occupied(mut occupied_entry) => {
-                        occupied_entry.get_mut().push([irow, icol]);
-                    }
-                }
-            }
-        }
-        Ok(Puzzle { shape, antennas })
+        Ok(Puzzle {
+            antennas: s.parse()?,
+        })
     }
 }
 
 impl Puzzle {
     fn process(&mut self) -> usize {
-        let mut antinodes = BTreeSet::<[usize; 2]>::new();
-        for antenna_positions in self.antennas.values() {
-            for start in antenna_positions {
-                for end in antenna_positions {
-                    if start == end {
-                        continue;
-                    }
-                    let start = start.map(|x| x as i64);
-                    let end = end.map(|x| x as i64);
-                    let diff = [end[0] - start[0], end[1] - start[1]];
-                    let mut harmonic = 1;
-                    loop {
-                        let antinode =
-                            [start[0] + diff[0] * harmonic, start[1] + diff[1] * harmonic];
-                        if antinode[0] < 0 || antinode[1] < 0 {
-                            break;
-                        }
-                        let antinode = antinode.map(|x| x as usize);
-                        if antinode[0] >= self.shape[0] || antinode[1] >= self.shape[1] {
-                            break;
-                        }
-                        antinodes.insert(antinode);
-                        harmonic += 1;
-                    }
-                }
-            }
-        }
-        antinodes.len()
+        self.antennas.all_antinodes(Rule::AllHarmonics).len()
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("08.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("08b", out));
     assert_eq!(out, 1235);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
 
     #[test]
     fn test() {
@@ -86,4 +43,17 @@ mod tests {
         let out = puzzle.process();
         assert_eq!(out, 34);
     }
+    #[test]
+    fn test_generated() {
+        let input = generate::day08(&mut Rng::new(8), [20, 30], 5);
+        input.parse::<Puzzle>().unwrap().process();
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("08_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
@@ -1,98 +1,57 @@
+use aoc24::claw_machine::{self, Machine};
+use aoc24::{answer::Answer, logging};
 use std::str::FromStr;
 
-#[derive(Debug)]
-struct Game {
-    buttons: [[usize; 2]; 2],
-    prize: [usize; 2],
-}
-
-impl Game {
-    fn solve(&self) -> Option<[usize; 2]> {
-        let [[ax, ay], [bx, by]] = self.buttons.map(|v| v.map(|x| x as f64));
-        let [px, py] = self.prize.map(|x| x as f64);
-        let b = (py * ax - ay * px) / (by * ax - bx * ay);
-        let a = (px - bx * b) / ax;
-        if a % 1.0 == 0.0 && b % 1.0 == 0.0 {
-            Some([a as usize, b as usize])
-        } else {
-            None
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Puzzle {
-    games: Vec<Game>,
-}
-
-impl From<Vec<&str>> for Game {
-    fn from(value: Vec<&str>) -> Self {
-        let parse_num = |s: &str| {
-            let (_, num) = s.split_once("+").unwrap();
-            num.parse::<usize>().unwrap()
-        };
-        let parse_button = |s: &str| {
-            let (_, rem) = s.split_once(": ").unwrap();
-            let (x, y) = rem.split_once(", ").unwrap();
-            [parse_num(x), parse_num(y)]
-        };
-        let parse_prize_num = |s: &str| {
-            let (_, num) = s.split_once("=").unwrap();
-            let offset = 10000000000000;
-            num.parse::<usize>().unwrap() + offset
-        };
-        let parse_prize = |s: &str| {
-            let (_, rem) = s.split_once(": ").unwrap();
-            let (x, y) = rem.split_once(", ").unwrap();
-            [parse_prize_num(x), parse_prize_num(y)]
-        };
-        let buttons = [parse_button(value[0]), parse_button(value[1])];
-        let prize = parse_prize(value[2]);
-        Game { buttons, prize }
-    }
+    games: Vec<Machine>,
 }
 
 impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = &mut s.lines();
-        let mut games = Vec::new();
-        loop {
-            games.push(Game::from(lines.take(3).collect::<Vec<_>>()));
-            if lines.next().is_none() {
-                break;
-            }
-        }
+        let mut games = claw_machine::parse_machines(s).ok_or(())?;
+        games
+            .iter_mut()
+            .try_for_each(|g| g.shift_prize(10000000000000))
+            .ok_or(())?;
         Ok(Puzzle { games })
     }
 }
 
 impl Puzzle {
-    fn process(&mut self) -> usize {
-        self.games
-            .iter()
-            .map(|g| {
-                if let Some([a, b]) = g.solve() {
-                    a * 3 + b
-                } else {
-                    0
-                }
-            })
-            .sum()
+    /// Tokens for every prize that can be won, None if the total overflows
+    fn process(&mut self) -> Option<usize> {
+        self.games.iter().try_fold(0usize, |total, g| {
+            total.checked_add(g.cost([3, 1]).unwrap_or(0))
+        })
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("13.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("13b", out));
     assert_eq!(out, 102255878088512);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
+    /// Values at the limits of `usize` and of the solver
+    const LARGE: &str = "Button A: X+18446744073709551615, Y+1
+Button B: X+1, Y+18446744073709551615
+Prize: X=18446744073709551615, Y=18446744073709551615
+
+Button A: X+281474976710656, Y+281474976710656
+Button B: X+1, Y+281474976710655
+Prize: X=281474976710656, Y=281474976710656
+";
 
     #[test]
     fn test_no_colinear() {
@@ -103,4 +62,24 @@ mod tests {
             assert_ne!(ratio_a, ratio_b);
         }
     }
+    #[test]
+    fn test_generated() {
+        let input = generate::day13(&mut Rng::new(13), 8, 0.5);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        assert!(LARGE.parse::<Puzzle>().is_err());
+        let seeds = [
+            include_str!("13_test.txt"),
+            LARGE,
+            &LARGE[LARGE.find("\n\n").unwrap() + 2..],
+        ];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
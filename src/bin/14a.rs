@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -15,14 +16,10 @@ impl Robot {
     }
     fn position_after_1d(&self, axis: usize, time: i64, room_size: [i64; 2]) -> i64 {
         assert!(self.position[axis] >= 0);
-        let p = self.position[axis] as u64;
-        let mut v = self.velocity[axis];
-        let r = room_size[axis] as u64;
-        while v < 0 {
-            v += r as i64;
-        }
-        let v = v as u64;
-        ((p + v * time as u64) % r) as i64
+        let r = room_size[axis];
+        // reduce everything by the room size first so nothing overflows
+        let v = self.velocity[axis].rem_euclid(r);
+        (self.position[axis] % r + v * (time % r)) % r
     }
 }
 
@@ -30,14 +27,21 @@ impl FromStr for Robot {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (position, velocity) = s.split_once(" ").unwrap();
-        let parse_numbers = |s: &str| {
-            let (_, s) = s.split_at(2);
-            let (x, y) = s.split_once(",").unwrap();
-            [x.parse::<i64>().unwrap(), y.parse::<i64>().unwrap()]
+        let (position, velocity) = s.split_once(" ").ok_or(())?;
+        let parse_numbers = |s: &str, prefix: &str| {
+            let (x, y) = s
+                .strip_prefix(prefix)
+                .ok_or(())?
+                .split_once(",")
+                .ok_or(())?;
+            let parse = |n: &str| n.parse::<i64>().map_err(|_| ());
+            Ok::<_, ()>([parse(x)?, parse(y)?])
         };
-        let position = parse_numbers(position);
-        let velocity = parse_numbers(velocity);
+        let position = parse_numbers(position, "p=")?;
+        let velocity = parse_numbers(velocity, "v=")?;
+        if position.iter().any(|&p| p < 0) {
+            return Err(());
+        }
         Ok(Robot { position, velocity })
     }
 }
@@ -54,7 +58,7 @@ impl FromStr for Puzzle {
         let count = s.lines().count();
         let mut robots = Vec::with_capacity(count);
         for line in s.lines() {
-            robots.push(line.parse::<Robot>().unwrap());
+            robots.push(line.parse::<Robot>()?);
         }
         Ok(Puzzle { robots })
     }
@@ -94,17 +98,20 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("14.txt").parse::<Puzzle>().unwrap();
     let time = 100;
     let room_size = [101, 103];
     let out = puzzle.process(time, room_size);
-    println!("{out}");
+    println!("{}", Answer::new("14a", out));
     assert_eq!(out, 211773366);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test() {
         let mut out = include_str!("14_test.txt").parse::<Puzzle>().unwrap();
@@ -123,4 +130,12 @@ mod tests {
         assert_eq!(robot.position_after(1, room_size), [4, 1]);
         assert_eq!(robot.position_after(2, room_size), [6, 5]);
     }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("14_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process(100, [11, 7]);
+            }
+        });
+    }
 }
@@ -1,4 +1,6 @@
+use aoc24::{answer::Answer, logging};
 // use core::time;
+use aoc24::parallel;
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -17,14 +19,10 @@ impl Robot {
     }
     fn position_after_1d(&self, axis: usize, time: i64, room_size: [i64; 2]) -> i64 {
         assert!(self.position[axis] >= 0);
-        let p = self.position[axis] as u64;
-        let mut v = self.velocity[axis];
-        let r = room_size[axis] as u64;
-        while v < 0 {
-            v += r as i64;
-        }
-        let v = v as u64;
-        ((p + v * time as u64) % r) as i64
+        let r = room_size[axis];
+        // reduce everything by the room size first so nothing overflows
+        let v = self.velocity[axis].rem_euclid(r);
+        (self.position[axis] % r + v * (time % r)) % r
     }
 }
 
@@ -32,14 +30,21 @@ impl FromStr for Robot {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (position, velocity) = s.split_once(" ").unwrap();
-        let parse_numbers = |s: &str| {
-            let (_, s) = s.split_at(2);
-            let (x, y) = s.split_once(",").unwrap();
-            [x.parse::<i64>().unwrap(), y.parse::<i64>().unwrap()]
+        let (position, velocity) = s.split_once(" ").ok_or(())?;
+        let parse_numbers = |s: &str, prefix: &str| {
+            let (x, y) = s
+                .strip_prefix(prefix)
+                .ok_or(())?
+                .split_once(",")
+                .ok_or(())?;
+            let parse = |n: &str| n.parse::<i64>().map_err(|_| ());
+            Ok::<_, ()>([parse(x)?, parse(y)?])
         };
-        let position = parse_numbers(position);
-        let velocity = parse_numbers(velocity);
+        let position = parse_numbers(position, "p=")?;
+        let velocity = parse_numbers(velocity, "v=")?;
+        if position.iter().any(|&p| p < 0) {
+            return Err(());
+        }
         Ok(Robot { position, velocity })
     }
 }
@@ -56,7 +61,7 @@ impl FromStr for Puzzle {
         let count = s.lines().count();
         let mut robots = Vec::with_capacity(count);
         for line in s.lines() {
-            robots.push(line.parse::<Robot>().unwrap());
+            robots.push(line.parse::<Robot>()?);
         }
         Ok(Puzzle { robots })
     }
@@ -109,18 +114,13 @@ fn symmetry(room: ArrayView2<bool>) -> usize {
     out
 }
 
-fn draw_room(room: ArrayView2<bool>) {
-    let shape = room.shape();
-    for irow in 0..shape[0] {
-        for icol in 0..shape[1] {
-            if room[[irow, icol]] {
-                print!("#")
-            } else {
-                print!(" ")
-            }
-        }
-        println!();
+fn draw_room(room: ArrayView2<bool>) -> String {
+    let mut out = String::with_capacity(room.len() + room.nrows());
+    for row in room.rows() {
+        out.extend(row.iter().map(|&robot| if robot { '#' } else { ' ' }));
+        out.push('\n');
     }
+    out
 }
 
 impl Puzzle {
@@ -151,31 +151,40 @@ impl Puzzle {
     //         p == r.position
     //     })
     // }
+    /// Find the most symmetric room before `max_time`, latest time wins a tie
+    /// None if there are no times to check
+    fn process(&self, room_size: [i64; 2], max_time: i64) -> Option<(i64, usize)> {
+        let max_time = usize::try_from(max_time).ok()?;
+        let symmetries = parallel::map_range(1..max_time, |time| {
+            symmetry(self.make_room(time as i64, room_size).view())
+        });
+        let (index, max_symmetry) = symmetries
+            .into_iter()
+            .enumerate()
+            .max_by_key(|&(_, symmetry)| symmetry)?;
+        Some((index as i64 + 1, max_symmetry))
+    }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("14.txt").parse::<Puzzle>().unwrap();
     let room_size = [101, 103];
-    let mut max_symmetry = 0;
     let max_time = 10403;
-    for time in 1..max_time {
-        let room = puzzle.make_room(time, room_size);
-        let symmetry = symmetry(room.view());
-        if symmetry >= max_symmetry {
-            println!("time: {time}, symmetry: {symmetry}");
-            draw_room(room.view());
-            max_symmetry = symmetry;
-        }
-        // if puzzle.match_start(time, room_size) {
-        //     println!("looped: time: {time}");
-        //     break;
-        // }
-    }
+    let (time, symmetry) = puzzle.process(room_size, max_time).unwrap();
+    println!("{}", Answer::new("14b", time).with("symmetry", symmetry));
+    log::info!(
+        "room after {time}\n{}",
+        draw_room(puzzle.make_room(time, room_size).view())
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test_position_after() {
         let position = [2, 4];
@@ -186,4 +195,26 @@ mod tests {
         assert_eq!(robot.position_after(1, room_size), [4, 1]);
         assert_eq!(robot.position_after(2, room_size), [6, 5]);
     }
+    #[test]
+    fn test_no_times() {
+        let puzzle = include_str!("14_test.txt").parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.process([11, 7], 1), None);
+        assert_eq!(puzzle.process([11, 7], -5), None);
+        assert_eq!(puzzle.process([11, 7], 2).map(|(time, _)| time), Some(1));
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day14(&mut Rng::new(14), 20, [11, 7]);
+        let puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process([11, 7], 77).is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("14_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process([11, 7], 100).unwrap();
+                puzzle.process([11, 7], 0);
+            }
+        });
+    }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -64,25 +65,42 @@ impl FromStr for Puzzle {
 
         let (map, robot) = {
             let nrows = map_lines.len();
-            let ncols = map_lines[0].len();
+            let ncols = map_lines.first().ok_or(())?.chars().count();
+            if map_lines.iter().any(|line| line.chars().count() != ncols) {
+                return Err(());
+            }
             let mut map = Vec::with_capacity(nrows * ncols);
-            let mut robot = [0, 0];
+            let mut robot = None;
             for (irow, line) in map_lines.iter().enumerate() {
                 for (icol, c) in line.chars().enumerate() {
                     map.push(match c {
                         '#' => Token::Wall,
                         'O' => Token::Box,
                         '.' => Token::None,
-                        '@' => {
-                            robot = [irow, icol];
+                        '@' if robot.is_none() => {
+                            robot = Some([irow, icol]);
                             Token::None
                         }
-                        _ => panic!(),
+                        _ => return Err(()),
                     });
                 }
             }
-            (Array2::from_shape_vec((nrows, ncols), map).unwrap(), robot)
+            (
+                Array2::from_shape_vec((nrows, ncols), map).map_err(|_| ())?,
+                robot.ok_or(())?,
+            )
+        };
+        // walls all round so the robot can never leave the map
+        let [nrows, ncols] = [map.nrows(), map.ncols()];
+        let edge = |(irow, icol): (usize, usize)| {
+            irow == 0 || icol == 0 || irow == nrows - 1 || icol == ncols - 1
         };
+        if map
+            .indexed_iter()
+            .any(|(pos, token)| edge(pos) && !matches!(token, Token::Wall))
+        {
+            return Err(());
+        }
 
         let mut directions =
             Vec::with_capacity(direction_lines.iter().map(|line| line.len()).sum());
@@ -93,7 +111,7 @@ impl FromStr for Puzzle {
                     '<' => Direction::Left,
                     '^' => Direction::Up,
                     'v' => Direction::Down,
-                    _ => panic!(),
+                    _ => return Err(()),
                 });
             }
         }
@@ -154,19 +172,22 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("15.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("15a", out));
     assert_eq!(out, 1495147);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test_small() {
         let out = include_str!("15_test_small.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
+        log::debug!("{:?}", out);
         let out = out.process();
         assert_eq!(out, 2028);
     }
@@ -176,4 +197,21 @@ mod tests {
         let out = out.process();
         assert_eq!(out, 10092);
     }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[
+                include_str!("15_test.txt"),
+                include_str!("15_test_small.txt"),
+                include_str!("15_testb.txt"),
+            ],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -65,9 +66,13 @@ impl FromStr for Puzzle {
 
         let (map, robot) = {
             let nrows = map_lines.len();
-            let ncols = map_lines[0].len() * 2;
+            let width = map_lines.first().ok_or(())?.chars().count();
+            if map_lines.iter().any(|line| line.chars().count() != width) {
+                return Err(());
+            }
+            let ncols = width * 2;
             let mut map = Vec::with_capacity(nrows * ncols);
-            let mut robot = [0, 0];
+            let mut robot = None;
             for (irow, line) in map_lines.iter().enumerate() {
                 for (icol, c) in line.chars().enumerate() {
                     match c {
@@ -83,17 +88,31 @@ impl FromStr for Puzzle {
                             map.push(Token::None);
                             map.push(Token::None);
                         }
-                        '@' => {
-                            robot = [irow, icol * 2];
+                        '@' if robot.is_none() => {
+                            robot = Some([irow, icol * 2]);
                             map.push(Token::None);
                             map.push(Token::None);
                         }
-                        _ => panic!(),
+                        _ => return Err(()),
                     }
                 }
             }
-            (Array2::from_shape_vec((nrows, ncols), map).unwrap(), robot)
+            (
+                Array2::from_shape_vec((nrows, ncols), map).map_err(|_| ())?,
+                robot.ok_or(())?,
+            )
+        };
+        // walls all round so the robot can never leave the map
+        let [nrows, ncols] = [map.nrows(), map.ncols()];
+        let edge = |(irow, icol): (usize, usize)| {
+            irow == 0 || icol == 0 || irow == nrows - 1 || icol == ncols - 1
         };
+        if map
+            .indexed_iter()
+            .any(|(pos, token)| edge(pos) && !matches!(token, Token::Wall))
+        {
+            return Err(());
+        }
 
         let mut directions =
             Vec::with_capacity(direction_lines.iter().map(|line| line.len()).sum());
@@ -104,7 +123,7 @@ impl FromStr for Puzzle {
                     '<' => Direction::Left,
                     '^' => Direction::Up,
                     'v' => Direction::Down,
-                    _ => panic!(),
+                    _ => return Err(()),
                 });
             }
         }
@@ -118,30 +137,21 @@ impl FromStr for Puzzle {
 }
 
 impl Puzzle {
-    fn process(&self, print: bool) -> usize {
+    fn process(&self) -> usize {
         let mut map = self.map.to_owned();
         let mut robot = self.robot;
-        if print {
-            Puzzle::print_map(map.view(), &robot);
-        }
+        log::debug!("initial map\n{}", Puzzle::draw_map(map.view(), &robot));
         for direction in &self.directions {
-            if print {
-                println!(
-                    "{}",
-                    match direction {
-                        Direction::Up => '^',
-                        Direction::Down => 'v',
-                        Direction::Left => '<',
-                        Direction::Right => '>',
-                    }
-                );
-            }
             if Puzzle::can_move(map.view(), &robot, direction) {
                 Puzzle::do_move(map.view_mut(), &robot, direction);
                 robot = direction.position_from(&robot).unwrap();
-                if print {
-                    Puzzle::print_map(map.view(), &robot);
-                }
+                log::trace!(
+                    "moved {:?}\n{}",
+                    direction,
+                    Puzzle::draw_map(map.view(), &robot)
+                );
+            } else {
+                log::trace!("blocked {:?}", direction);
             }
         }
         map.indexed_iter()
@@ -228,17 +238,11 @@ impl Puzzle {
             Token::Wall => panic!(),
         }
     }
-    fn print_map(map: ArrayView2<Token>, robot: &[usize; 2]) {
-        let mut current_irow = 0;
-        let robot = (robot[0], robot[1]);
-        for (pos, token) in map.indexed_iter() {
-            if pos.0 != current_irow {
-                println!();
-                current_irow = pos.0;
-            }
-            print!(
-                "{}",
-                if pos == robot {
+    fn draw_map(map: ArrayView2<Token>, robot: &[usize; 2]) -> String {
+        let mut out = String::with_capacity(map.len() + map.nrows());
+        for (irow, row) in map.rows().into_iter().enumerate() {
+            for (icol, token) in row.iter().enumerate() {
+                out.push(if [irow, icol] == *robot {
                     '@'
                 } else {
                     match token {
@@ -247,27 +251,54 @@ impl Puzzle {
                         Token::BoxRight => ']',
                         Token::None => '.',
                     }
-                }
-            );
+                });
+            }
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("15.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(false);
-    println!("{out}");
+    let out = puzzle.process();
+    println!("{}", Answer::new("15b", out));
     assert_eq!(out, 1524905);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let out = include_str!("15_test.txt").parse::<Puzzle>().unwrap();
-        let out = out.process(true);
+        let out = out.process();
         assert_eq!(out, 9021);
     }
+    #[test]
+    fn test_generated() {
+        let input = generate::day15(&mut Rng::new(15), [8, 10], 0.2, 100);
+        input.parse::<Puzzle>().unwrap().process();
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[
+                include_str!("15_test.txt"),
+                include_str!("15_test_small.txt"),
+                include_str!("15_testb.txt"),
+            ],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
+    }
 }
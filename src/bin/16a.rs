@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, grid, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -42,30 +43,38 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let mut map = Vec::with_capacity(nrows * ncols);
-        let mut start = [0, 0];
-        let mut end = [0, 0];
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, c) in line.chars().enumerate() {
-                map.push(match c {
-                    '.' => Token::None,
-                    '#' => Token::Wall,
-                    'S' => {
-                        start = [irow, icol];
-                        Token::None
-                    }
-                    'E' => {
-                        end = [irow, icol];
-                        Token::None
-                    }
-                    _ => panic!(),
-                });
+        let mut start = None;
+        let mut end = None;
+        let map = grid::parse(s, |pos, c| match c {
+            '.' => Some(Token::None),
+            '#' => Some(Token::Wall),
+            'S' if start.is_none() => {
+                start = Some(pos);
+                Some(Token::None)
             }
+            'E' if end.is_none() => {
+                end = Some(pos);
+                Some(Token::None)
+            }
+            _ => None,
+        })
+        .ok_or(())?;
+        // walls all round so the reindeer can never leave the map
+        let [nrows, ncols] = [map.nrows(), map.ncols()];
+        let edge = |(irow, icol): (usize, usize)| {
+            irow == 0 || icol == 0 || irow == nrows - 1 || icol == ncols - 1
+        };
+        if map
+            .indexed_iter()
+            .any(|(pos, token)| edge(pos) && !matches!(token, Token::Wall))
+        {
+            return Err(());
         }
-        let map = Array2::from_shape_vec((nrows, ncols), map).unwrap();
-        Ok(Puzzle { map, start, end })
+        Ok(Puzzle {
+            map,
+            start: start.ok_or(())?,
+            end: end.ok_or(())?,
+        })
     }
 }
 
@@ -83,7 +92,8 @@ struct State {
 }
 
 impl Puzzle {
-    fn process(&self, print: bool) -> usize {
+    /// Lowest score to reach the end, None if it can't be reached
+    fn process(&self) -> Option<usize> {
         let mut state = State {
             scores: Array2::<Option<usize>>::from_elem(self.map.raw_dim(), None),
             directions: Array2::<Option<Direction>>::from_elem(self.map.raw_dim(), None),
@@ -94,7 +104,7 @@ impl Puzzle {
         let mut ends = [vec![self.start], Vec::new()];
         loop {
             let (e0, e1) = ends.split_at_mut(1);
-            let (current, next) = if state.steps % 2 == 0 {
+            let (current, next) = if state.steps.is_multiple_of(2) {
                 (&e0[0], &mut e1[0])
             } else {
                 (&e1[0], &mut e0[0])
@@ -108,10 +118,12 @@ impl Puzzle {
             }
             state.steps += 1;
         }
-        if print {
-            self.print_path(&state);
-        }
-        state.scores[self.end].unwrap()
+        log::debug!(
+            "score {:?}, directions taken\n{}",
+            state.scores[self.end],
+            self.draw_path(&state)
+        );
+        state.scores[self.end]
     }
     fn process_pos(&self, pos: &[usize; 2], state: &mut State) -> Vec<[usize; 2]> {
         let mut next_ends = Vec::new();
@@ -155,62 +167,66 @@ impl Puzzle {
         }
         possible_directions
     }
-    fn print_path(&self, state: &State) {
-        let score = state.scores[self.end];
-        println!();
-        if score.is_none() {
-            println!("Score: None");
-        } else {
-            println!("Score: {}", score.unwrap());
-        }
-        let mut current_row = 0;
-        for (pos, token) in self.map.indexed_iter() {
-            let pos = [pos.0, pos.1];
-            if current_row != pos[0] {
-                println!();
-                current_row = pos[0];
-            }
-            print!(
-                "{}",
-                if let Some(direction) = state.directions[pos] {
+    fn draw_path(&self, state: &State) -> String {
+        let mut out = String::with_capacity(self.map.len() + self.map.nrows());
+        for (row, directions) in self.map.rows().into_iter().zip(state.directions.rows()) {
+            for (token, direction) in row.iter().zip(directions) {
+                out.push(if let Some(direction) = direction {
                     match direction {
-                        Direction::Down => "v",
-                        Direction::Up => "^",
-                        Direction::Left => "<",
-                        Direction::Right => ">",
+                        Direction::Down => 'v',
+                        Direction::Up => '^',
+                        Direction::Left => '<',
+                        Direction::Right => '>',
                     }
                 } else {
                     match token {
-                        Token::None => ".",
-                        Token::Wall => "#",
+                        Token::None => '.',
+                        Token::Wall => '#',
                     }
-                }
-            );
+                });
+            }
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("16.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(false);
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("16a", out));
     assert_eq!(out, 66404);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test_a() {
         let out = include_str!("16_test_a.txt").parse::<Puzzle>().unwrap();
-        let out = out.process(true);
-        assert_eq!(out, 7036);
+        let out = out.process();
+        assert_eq!(out, Some(7036));
     }
     #[test]
     fn test_b() {
         let out = include_str!("16_test_b.txt").parse::<Puzzle>().unwrap();
-        let out = out.process(false);
-        assert_eq!(out, 11048);
+        let out = out.process();
+        assert_eq!(out, Some(11048));
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[include_str!("16_test_a.txt"), include_str!("16_test_b.txt")],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
     }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, grid, logging};
 use ndarray::prelude::*;
 use std::{cmp::Ordering, collections::BTreeSet, str::FromStr};
 
@@ -92,30 +93,38 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let mut map = Vec::with_capacity(nrows * ncols);
-        let mut start = [0, 0];
-        let mut end = [0, 0];
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, c) in line.chars().enumerate() {
-                map.push(match c {
-                    '.' => Token::None,
-                    '#' => Token::Wall,
-                    'S' => {
-                        start = [irow, icol];
-                        Token::None
-                    }
-                    'E' => {
-                        end = [irow, icol];
-                        Token::None
-                    }
-                    _ => panic!(),
-                });
+        let mut start = None;
+        let mut end = None;
+        let map = grid::parse(s, |pos, c| match c {
+            '.' => Some(Token::None),
+            '#' => Some(Token::Wall),
+            'S' if start.is_none() => {
+                start = Some(pos);
+                Some(Token::None)
             }
+            'E' if end.is_none() => {
+                end = Some(pos);
+                Some(Token::None)
+            }
+            _ => None,
+        })
+        .ok_or(())?;
+        // walls all round so the reindeer can never leave the map
+        let [nrows, ncols] = [map.nrows(), map.ncols()];
+        let edge = |(irow, icol): (usize, usize)| {
+            irow == 0 || icol == 0 || irow == nrows - 1 || icol == ncols - 1
+        };
+        if map
+            .indexed_iter()
+            .any(|(pos, token)| edge(pos) && !matches!(token, Token::Wall))
+        {
+            return Err(());
         }
-        let map = Array2::from_shape_vec((nrows, ncols), map).unwrap();
-        Ok(Puzzle { map, start, end })
+        Ok(Puzzle {
+            map,
+            start: start.ok_or(())?,
+            end: end.ok_or(())?,
+        })
     }
 }
 
@@ -128,7 +137,9 @@ struct State {
 }
 
 impl Puzzle {
-    fn process(&self, print: bool) -> usize {
+    /// Tiles on any best path and the best score, no tiles if the end
+    /// can't be reached
+    fn process(&self) -> (usize, Option<usize>) {
         let map_shape = self.map.shape();
         let records_shape = [map_shape[0], map_shape[1], 4];
         let mut state = State {
@@ -141,7 +152,7 @@ impl Puzzle {
         let mut ends = [vec![start], Vec::new()];
         loop {
             let (e0, e1) = ends.split_at_mut(1);
-            let (current, next) = if state.steps % 2 == 0 {
+            let (current, next) = if state.steps.is_multiple_of(2) {
                 (&e0[0], &mut e1[0])
             } else {
                 (&e1[0], &mut e0[0])
@@ -155,14 +166,21 @@ impl Puzzle {
             }
             state.steps += 1;
         }
-        if print {
-            self.print_path(&state);
-        }
-        self.trace_back(&state, &self.best_end_position(&state).unwrap())
+        // no tiles are on a best path if the end can't be reached
+        let Some(end) = self.best_end_position(&state) else {
+            return (0, None);
+        };
+        let tiles = self
+            .trace_back(&state, &end)
             .iter()
             .map(|x| [x[0], x[1]])
-            .collect::<BTreeSet<[usize; 2]>>()
-            .len()
+            .collect::<BTreeSet<[usize; 2]>>();
+        log::debug!(
+            "score {:?}, tiles on a best path\n{}",
+            state.scores[end],
+            self.draw_path(&tiles)
+        );
+        (tiles.len(), state.scores[end])
     }
     fn best_end_position(&self, state: &State) -> Option<[usize; 3]> {
         let mut lowest_score = None;
@@ -205,34 +223,40 @@ impl Puzzle {
             if let Some(existing_score) = state.scores[next_position] {
                 match next_score.cmp(&existing_score) {
                     Ordering::Less => {
-                        // println!(
-                        //     "DEBUG: order less: {:?}->{:?} {} < {}",
-                        //     pos, next_position, next_score, existing_score
-                        // );
+                        log::trace!(
+                            "order less: {:?} -> {:?} {} < {}",
+                            pos,
+                            next_position,
+                            next_score,
+                            existing_score
+                        );
                         next_ends.push(next_position);
                         state.scores[next_position] = Some(next_score);
                         state.previous[next_position].clear();
                         state.previous[next_position].push(pos);
                     }
                     Ordering::Equal => {
-                        // println!(
-                        //     "DEBUG: order equal: {:?}->{:?} {} == {}",
-                        //     pos, next_position, next_score, existing_score
-                        // );
+                        log::trace!(
+                            "order equal: {:?} -> {:?} {} == {}",
+                            pos,
+                            next_position,
+                            next_score,
+                            existing_score
+                        );
                         state.previous[next_position].push(pos);
                     }
                     Ordering::Greater => {
-                        // println!(
-                        //     "DEBUG: order greater: {:?}->{:?} {} > {}",
-                        //     pos, next_position, next_score, existing_score
-                        // );
+                        log::trace!(
+                            "order greater: {:?} -> {:?} {} > {}",
+                            pos,
+                            next_position,
+                            next_score,
+                            existing_score
+                        );
                     }
                 }
             } else {
-                // println!(
-                //     "DEBUG: new item: {:?}->{:?} {}",
-                //     pos, next_position, next_score
-                // );
+                log::trace!("new item: {:?} -> {:?} {}", pos, next_position, next_score);
                 next_ends.push(next_position);
                 state.scores[next_position] = Some(next_score);
                 state.previous[next_position].push(pos);
@@ -260,62 +284,70 @@ impl Puzzle {
         }
         possible_positions
     }
-    fn print_path(&self, state: &State) {
-        let path = self
-            .trace_back(state, &self.best_end_position(state).unwrap())
-            .iter()
-            .map(|x| [x[0], x[1]])
-            .collect::<BTreeSet<[usize; 2]>>();
-        // let score = state.scores[self.end];
-        println!();
-        // if score.is_none() {
-        //     println!("Score: None");
-        // } else {
-        //     println!("Score: {}", score.unwrap());
-        // }
-        let mut current_row = 0;
-        for (pos, token) in self.map.indexed_iter() {
-            let pos = [pos.0, pos.1];
-            if current_row != pos[0] {
-                println!();
-                current_row = pos[0];
-            }
-            if path.contains(&pos) {
-                print!("O");
-            } else {
-                print!(
-                    "{}",
+    fn draw_path(&self, path: &BTreeSet<[usize; 2]>) -> String {
+        let mut out = String::with_capacity(self.map.len() + self.map.nrows());
+        for (irow, row) in self.map.rows().into_iter().enumerate() {
+            for (icol, token) in row.iter().enumerate() {
+                out.push(if path.contains(&[irow, icol]) {
+                    'O'
+                } else {
                     match token {
-                        Token::None => ".",
-                        Token::Wall => "#",
+                        Token::None => '.',
+                        Token::Wall => '#',
                     }
-                );
+                });
             }
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("16.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(false);
-    println!("{out}");
+    let (out, score) = puzzle.process();
+    let score = score.unwrap();
+    println!("{}", Answer::new("16b", out).with("score", score));
     assert_eq!(out, 433);
+    assert_eq!(score, 66404);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test_a() {
         let out = include_str!("16_test_a.txt").parse::<Puzzle>().unwrap();
-        let out = out.process(true);
-        assert_eq!(out, 45);
+        let out = out.process();
+        assert_eq!(out, (45, Some(7036)));
     }
     #[test]
     fn test_b() {
         let out = include_str!("16_test_b.txt").parse::<Puzzle>().unwrap();
-        let out = out.process(false);
-        assert_eq!(out, 64);
+        let out = out.process();
+        assert_eq!(out, (64, Some(11048)));
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day16(&mut Rng::new(16), [15, 21], 0.1);
+        let (_, score) = input.parse::<Puzzle>().unwrap().process();
+        assert!(score.is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[include_str!("16_test_a.txt"), include_str!("16_test_b.txt")],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
     }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use std::str::FromStr;
 
 enum Instruction {
@@ -58,9 +59,16 @@ impl Computer {
             _ => panic!(),
         }
     }
+    /// Register A divided by 2 to the power of `combo`
+    fn divide(&self, combo: usize) -> usize {
+        u32::try_from(combo)
+            .ok()
+            .and_then(|shift| self.registers[0].checked_shr(shift))
+            .unwrap_or(0)
+    }
     fn adv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[0] /= 2_usize.pow(combo as u32);
+        self.registers[0] = self.divide(combo);
         None
     }
     fn bxl(&mut self, literal: usize) -> Option<usize> {
@@ -87,19 +95,34 @@ impl Computer {
     }
     fn bdv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[1] = self.registers[0] / 2_usize.pow(combo as u32);
+        self.registers[1] = self.divide(combo);
         None
     }
     fn cdv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[2] = self.registers[0] / 2_usize.pow(combo as u32);
+        self.registers[2] = self.divide(combo);
         None
     }
+    /// Also halted with an opcode but no operand left
     fn halted(&self) -> bool {
-        self.instruction_pointer >= self.program.len()
+        self.instruction_pointer + 1 >= self.program.len()
     }
 }
 
+/// Programs that run longer than this are assumed never to halt
+const MAX_STEPS: usize = 100_000;
+
+/// Every instruction can run: 3 bit numbers, jumps only to even addresses
+/// so operands are never run as opcodes, and no combo operand 7
+fn valid_program(program: &[usize]) -> bool {
+    program.iter().all(|&x| x < 8)
+        && program.chunks_exact(2).all(|pair| match pair[0] {
+            3 => pair[1] % 2 == 0,
+            0 | 2 | 5 | 6 | 7 => pair[1] != 7,
+            _ => true,
+        })
+}
+
 #[derive(Debug)]
 struct Puzzle {
     registers: [usize; 3],
@@ -111,59 +134,70 @@ impl FromStr for Puzzle {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<&str> = s.lines().collect();
-        let read_register = |line: &str| {
-            let (_, line) = line.split_once(": ").unwrap();
-            line.parse::<usize>().unwrap()
+        let read_register = |line: Option<&&str>| {
+            let (_, line) = line.ok_or(())?.split_once(": ").ok_or(())?;
+            line.parse::<usize>().map_err(|_| ())
         };
         let registers = [
-            read_register(lines[0]),
-            read_register(lines[1]),
-            read_register(lines[2]),
+            read_register(lines.first())?,
+            read_register(lines.get(1))?,
+            read_register(lines.get(2))?,
         ];
-        let program = lines[4];
-        let (_, program) = program.split_once(": ").unwrap();
+        let program = lines.get(4).ok_or(())?;
+        let (_, program) = program.split_once(": ").ok_or(())?;
         let program = program
             .split(",")
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect();
+            .map(|s| s.parse::<usize>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !valid_program(&program) {
+            return Err(());
+        }
 
         Ok(Puzzle { registers, program })
     }
 }
 
 impl Puzzle {
-    fn process(&self) -> String {
+    /// Output of the program, None if it doesn't halt
+    fn process(&self) -> Option<String> {
         let mut computer = Computer {
             registers: self.registers,
             instruction_pointer: 0,
             program: self.program.clone(),
         };
         let mut out = Vec::new();
-        while !computer.halted() {
+        for _ in 0..MAX_STEPS {
+            if computer.halted() {
+                return Some(out.join(","));
+            }
             if let Some(o) = computer.run_instruction() {
                 out.push(o.to_string())
             }
         }
-        out.join(",")
+        None
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("17.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("17a", out.as_str()));
     assert_eq!(out, "2,1,4,7,6,0,3,1,4")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let out = include_str!("17_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
+        log::debug!("{:?}", out);
         let out = out.process();
-        assert_eq!(out, "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(out.as_deref(), Some("4,6,3,5,6,3,5,2,1,0"));
     }
     // If register C contains 9, the program 2,6 would set register B to 1.
     #[test]
@@ -201,4 +235,23 @@ mod tests {
         computer.run_instruction();
         assert_eq!(computer.registers[1], 26);
     }
+    #[test]
+    fn test_generated() {
+        let input = generate::day17(&mut Rng::new(17), 30);
+        let out = input.parse::<Puzzle>().unwrap().process().unwrap();
+        assert!(!out.is_empty());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[include_str!("17_test.txt"), include_str!("17_test_b.txt")],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
+    }
 }
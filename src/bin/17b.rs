@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug)]
@@ -74,9 +75,16 @@ impl Computer {
             _ => panic!(),
         }
     }
+    /// Register A divided by 2 to the power of `combo`
+    fn divide(&self, combo: usize) -> usize {
+        u32::try_from(combo)
+            .ok()
+            .and_then(|shift| self.registers[0].checked_shr(shift))
+            .unwrap_or(0)
+    }
     fn adv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[0] /= 2_usize.pow(combo as u32);
+        self.registers[0] = self.divide(combo);
         None
     }
     fn bxl(&mut self, literal: usize) -> Option<usize> {
@@ -103,19 +111,34 @@ impl Computer {
     }
     fn bdv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[1] = self.registers[0] / 2_usize.pow(combo as u32);
+        self.registers[1] = self.divide(combo);
         None
     }
     fn cdv(&mut self, combo: usize) -> Option<usize> {
         let combo: usize = self.resolve_combo(combo);
-        self.registers[2] = self.registers[0] / 2_usize.pow(combo as u32);
+        self.registers[2] = self.divide(combo);
         None
     }
+    /// Also halted with an opcode but no operand left
     fn halted(&self, program: &[usize]) -> bool {
-        self.instruction_pointer >= program.len()
+        self.instruction_pointer + 1 >= program.len()
     }
 }
 
+/// Programs that run longer than this are assumed never to halt
+const MAX_STEPS: usize = 100_000;
+
+/// Every instruction can run: 3 bit numbers, jumps only to even addresses
+/// so operands are never run as opcodes, and no combo operand 7
+fn valid_program(program: &[usize]) -> bool {
+    program.iter().all(|&x| x < 8)
+        && program.chunks_exact(2).all(|pair| match pair[0] {
+            3 => pair[1] % 2 == 0,
+            0 | 2 | 5 | 6 | 7 => pair[1] != 7,
+            _ => true,
+        })
+}
+
 #[derive(Debug)]
 struct Puzzle {
     program: Vec<usize>,
@@ -126,12 +149,15 @@ impl FromStr for Puzzle {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<&str> = s.lines().collect();
-        let program = lines[4];
-        let (_, program) = program.split_once(": ").unwrap();
+        let program = lines.get(4).ok_or(())?;
+        let (_, program) = program.split_once(": ").ok_or(())?;
         let program = program
             .split(",")
-            .map(|s| s.parse::<usize>().unwrap())
-            .collect();
+            .map(|s| s.parse::<usize>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !valid_program(&program) {
+            return Err(());
+        }
 
         Ok(Puzzle { program })
     }
@@ -147,13 +173,16 @@ impl Puzzle {
                 instruction_pointer: 0,
             };
             let mut output = None;
-            while !computer.halted(program) {
+            for _ in 0..MAX_STEPS {
+                if computer.halted(program) {
+                    break;
+                }
                 if let Some(o) = computer.run_instruction(program) {
                     output = Some(o);
                     break;
                 }
             }
-            if output.unwrap() == target {
+            if output == Some(target) {
                 out.push(num);
             }
         }
@@ -162,36 +191,40 @@ impl Puzzle {
     fn search(&self, base: usize, target_index: usize) -> Option<usize> {
         let candidates = self.find_candidates(base, self.program[target_index]);
         for candidate in candidates {
-            let new_base = (base + candidate) * 8;
             if target_index == 0 {
                 return Some(base + candidate);
             }
+            let new_base = (base + candidate).checked_mul(8)?;
             if let Some(output) = self.search(new_base, target_index - 1) {
                 return Some(output);
             }
         }
         None
     }
-    fn process(&self) -> usize {
-        self.search(0, self.program.len() - 1).unwrap()
+    /// Lowest register A that makes the program print itself, if found
+    fn process(&self) -> Option<usize> {
+        self.search(0, self.program.len().checked_sub(1)?)
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("17.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("17b", out));
     assert_eq!(out, 266932601404433)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test_b() {
         let out = include_str!("17_test_b.txt").parse::<Puzzle>().unwrap();
         let out = out.process();
-        assert_eq!(out, 117440);
+        assert_eq!(out, Some(117440));
     }
     // If register C contains 9, the program 2,6 would set register B to 1.
     #[test]
@@ -247,4 +280,17 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[include_str!("17_test.txt"), include_str!("17_test_b.txt")],
+            300,
+            |s| {
+                if let Ok(puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process();
+                }
+            },
+        );
+    }
 }
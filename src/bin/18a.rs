@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -42,8 +43,9 @@ impl FromStr for Puzzle {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut blocks = Vec::with_capacity(s.lines().count());
         for line in s.lines() {
-            let (x, y) = line.split_once(",").unwrap();
-            blocks.push([x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()])
+            let (x, y) = line.split_once(",").ok_or(())?;
+            let parse = |n: &str| n.parse::<usize>().map_err(|_| ());
+            blocks.push([parse(x)?, parse(y)?])
         }
         Ok(Puzzle { blocks })
     }
@@ -63,6 +65,12 @@ struct State {
 }
 
 impl Puzzle {
+    /// Every block lands inside a map of this shape
+    fn blocks_inside(&self, shape: [usize; 2]) -> bool {
+        self.blocks
+            .iter()
+            .all(|block| block[1] < shape[0] && block[0] < shape[1])
+    }
     fn make_map(&self, shape: [usize; 2], falls: usize) -> Array2<Token> {
         let mut map = Array2::from_elem(shape, Token::None);
         for block in &self.blocks[0..falls] {
@@ -71,7 +79,12 @@ impl Puzzle {
         }
         map
     }
-    fn process(&self, shape: [usize; 2], falls: usize) -> usize {
+    /// Fewest steps to the exit, None if it can't be reached or the
+    /// blocks don't fit the map
+    fn process(&self, shape: [usize; 2], falls: usize) -> Option<usize> {
+        if falls > self.blocks.len() || !self.blocks_inside(shape) {
+            return None;
+        }
         let map = self.make_map(shape, falls);
         let step_map = Array2::from_elem(map.raw_dim(), None);
         let mut state = State {
@@ -80,14 +93,14 @@ impl Puzzle {
             steps: 0,
         };
         self.solve_maze(&mut state);
-        self.print_path(&state);
-        state.step_map[[shape[0] - 1, shape[1] - 1]].unwrap()
+        log::debug!("map after {falls} falls\n{}", self.draw_map(&state));
+        state.step_map[[shape[0] - 1, shape[1] - 1]]
     }
     fn solve_maze(&self, state: &mut State) {
         let mut ends = [vec![[0, 0]], Vec::new()];
         loop {
             let (e0, e1) = ends.split_at_mut(1);
-            let (current, next) = if state.steps % 2 == 0 {
+            let (current, next) = if state.steps.is_multiple_of(2) {
                 (&e0[0], &mut e1[0])
             } else {
                 (&e1[0], &mut e0[0])
@@ -133,42 +146,45 @@ impl Puzzle {
         }
         possible_directions
     }
-    fn print_path(&self, state: &State) {
-        println!();
-        let mut current_row = 0;
-        for (pos, token) in state.map.indexed_iter() {
-            let pos = [pos.0, pos.1];
-            if current_row != pos[0] {
-                println!();
-                current_row = pos[0];
-            }
-            print!(
-                "{}",
-                match token {
-                    Token::None => ".",
-                    Token::Wall => "#",
-                }
-            );
+    fn draw_map(&self, state: &State) -> String {
+        let mut out = String::with_capacity(state.map.len() + state.map.nrows());
+        for row in state.map.rows() {
+            out.extend(row.iter().map(|token| match token {
+                Token::None => '.',
+                Token::Wall => '#',
+            }));
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("18.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process([71, 71], 1024);
-    println!("{out}");
+    let out = puzzle.process([71, 71], 1024).unwrap();
+    println!("{}", Answer::new("18a", out));
     assert_eq!(out, 338);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test_a() {
         let out = include_str!("18_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
+        log::debug!("{:?}", out);
         let out = out.process([7, 7], 12);
-        assert_eq!(out, 22);
+        assert_eq!(out, Some(22));
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("18_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process([7, 7], 12);
+            }
+        });
     }
 }
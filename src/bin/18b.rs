@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -42,8 +43,9 @@ impl FromStr for Puzzle {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut blocks = Vec::with_capacity(s.lines().count());
         for line in s.lines() {
-            let (x, y) = line.split_once(",").unwrap();
-            blocks.push([x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()])
+            let (x, y) = line.split_once(",").ok_or(())?;
+            let parse = |n: &str| n.parse::<usize>().map_err(|_| ());
+            blocks.push([parse(x)?, parse(y)?])
         }
         Ok(Puzzle { blocks })
     }
@@ -64,6 +66,12 @@ struct State {
 }
 
 impl Puzzle {
+    /// Every block lands inside a map of this shape
+    fn blocks_inside(&self, shape: [usize; 2]) -> bool {
+        self.blocks
+            .iter()
+            .all(|block| block[1] < shape[0] && block[0] < shape[1])
+    }
     fn add_block(&self, state: &mut State, index: usize) {
         let block = &self.blocks[index];
         let pos = [block[1], block[0]];
@@ -85,7 +93,12 @@ impl Puzzle {
             }
         }
     }
-    fn process(&self, shape: [usize; 2]) -> String {
+    /// Index of the first block that cuts off the exit, None if the blocks
+    /// don't fit the map or there are too few to search
+    fn blocking_index(&self, shape: [usize; 2]) -> Option<usize> {
+        if self.blocks.len() < 2 || !self.blocks_inside(shape) {
+            return None;
+        }
         let map = Array2::from_elem(shape, Token::None);
         let step_map = Array2::from_elem(map.raw_dim(), None);
         let mut state = State {
@@ -110,9 +123,12 @@ impl Puzzle {
             }
         }
         self.adjust_map(&mut state, bounds[1]);
-        self.print_path(&state);
-        let out = self.blocks[bounds[1]];
-        format!("{},{}", out[0], out[1])
+        log::debug!(
+            "map once block {} falls\n{}",
+            bounds[1],
+            self.draw_map(&state)
+        );
+        Some(bounds[1])
     }
     fn solve_maze(&self, state: &mut State) {
         // reset solution
@@ -121,7 +137,7 @@ impl Puzzle {
         let mut ends = [vec![[0, 0]], Vec::new()];
         loop {
             let (e0, e1) = ends.split_at_mut(1);
-            let (current, next) = if state.steps % 2 == 0 {
+            let (current, next) = if state.steps.is_multiple_of(2) {
                 (&e0[0], &mut e1[0])
             } else {
                 (&e1[0], &mut e0[0])
@@ -167,42 +183,59 @@ impl Puzzle {
         }
         possible_directions
     }
-    fn print_path(&self, state: &State) {
-        println!();
-        let mut current_row = 0;
-        for (pos, token) in state.map.indexed_iter() {
-            let pos = [pos.0, pos.1];
-            if current_row != pos[0] {
-                println!();
-                current_row = pos[0];
-            }
-            print!(
-                "{}",
-                match token {
-                    Token::None => ".",
-                    Token::Wall => "#",
-                }
-            );
+    fn draw_map(&self, state: &State) -> String {
+        let mut out = String::with_capacity(state.map.len() + state.map.nrows());
+        for row in state.map.rows() {
+            out.extend(row.iter().map(|token| match token {
+                Token::None => '.',
+                Token::Wall => '#',
+            }));
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("18.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process([71, 71]);
-    println!("{out}");
+    let index = puzzle.blocking_index([71, 71]).unwrap();
+    let block = puzzle.blocks[index];
+    let out = format!("{},{}", block[0], block[1]);
+    println!(
+        "{}",
+        Answer::new("18b", out.as_str())
+            .with("byte", index)
+            .with("position", block)
+    );
     assert_eq!(out, "20,44");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test_a() {
         let out = include_str!("18_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
-        let out = out.process([7, 7]);
-        assert_eq!(out, "6,1");
+        log::debug!("{:?}", out);
+        assert_eq!(out.blocking_index([7, 7]), Some(20));
+        assert_eq!(out.blocks[20], [6, 1]);
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day18(&mut Rng::new(18), 7, 40);
+        let puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.blocking_index([7, 7]).is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("18_test.txt")], 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.blocking_index([7, 7]);
+            }
+        });
     }
 }
@@ -1,34 +1,10 @@
-use std::{
-    collections::{BTreeSet, HashSet},
-    str::FromStr,
-};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Token {
-    White,
-    Blue,
-    Black,
-    Red,
-    Green,
-}
-
-impl From<char> for Token {
-    fn from(value: char) -> Self {
-        match value {
-            'w' => Token::White,
-            'u' => Token::Blue,
-            'b' => Token::Black,
-            'r' => Token::Red,
-            'g' => Token::Green,
-            _ => panic!(),
-        }
-    }
-}
+use aoc24::towels::{self, Token, Towels};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Puzzle {
-    available: HashSet<Vec<Token>>,
-    max_available_len: usize,
+    towels: Towels,
     needed: Vec<Vec<Token>>,
 }
 
@@ -37,85 +13,61 @@ impl FromStr for Puzzle {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<&str> = s.lines().collect();
-        let available: HashSet<Vec<Token>> = lines[0]
-            .split(", ")
-            .map(|s| s.chars().map(Token::from).collect())
-            .collect();
-        let max_available_len = available
+        let towels = lines.first().ok_or(())?.parse()?;
+        let needed = lines
+            .get(2..)
+            .ok_or(())?
             .iter()
-            .map(|a| a.len())
-            .fold(0, |a, len| a.max(len));
-        let needed = lines[2..]
-            .iter()
-            .map(|line| line.chars().map(Token::from).collect())
-            .collect();
-        Ok(Puzzle {
-            available,
-            max_available_len,
-            needed,
-        })
+            .map(|line| towels::parse_tokens(line))
+            .collect::<Option<_>>()
+            .ok_or(())?;
+        Ok(Puzzle { towels, needed })
     }
 }
 
 impl Puzzle {
     fn process(&self) -> usize {
-        let mut out = 0;
-        for needed in &self.needed {
-            if self.min_towels(needed).is_some() {
-                out += 1;
-            }
-        }
-        out
-    }
-    fn min_towels(&self, needed: &[Token]) -> Option<usize> {
-        let mut steps = 0;
-        let mut ends = [BTreeSet::from([0]), BTreeSet::new()];
-        loop {
-            let (e0, e1) = ends.split_at_mut(1);
-            let (current, next) = if steps % 2 == 0 {
-                (&e0[0], &mut e1[0])
-            } else {
-                (&e1[0], &mut e0[0])
-            };
-            next.clear();
-            for &i in current {
-                for len in 1..=self.max_available_len {
-                    if i + len > needed.len() {
-                        continue;
-                    }
-                    if !self.available.contains(&needed[i..i + len]) {
-                        continue;
-                    }
-                    if i + len == needed.len() {
-                        return Some(steps + 1);
-                    }
-                    next.insert(i + len);
-                }
-            }
-            steps += 1;
-            if next.is_empty() {
-                break;
-            }
-        }
-        None
+        self.needed
+            .iter()
+            .filter(|needed| self.towels.possible(needed))
+            .count()
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("19.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("19a", out));
     assert_eq!(out, 360);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
+    /// A design with more arrangements than fit in a `usize`
+    const LONG: &str = "r, rr
+
+rrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrr
+";
+
     #[test]
     fn test() {
         let out = include_str!("19_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
         let out = out.process();
         assert_eq!(out, 6);
     }
+    #[test]
+    fn test_fuzz() {
+        let out = LONG.parse::<Puzzle>().unwrap().process();
+        assert_eq!(out, 1);
+        let seeds = [include_str!("19_test.txt"), LONG];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
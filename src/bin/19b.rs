@@ -1,31 +1,10 @@
-use std::{collections::HashSet, str::FromStr};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Token {
-    White,
-    Blue,
-    Black,
-    Red,
-    Green,
-}
-
-impl From<char> for Token {
-    fn from(value: char) -> Self {
-        match value {
-            'w' => Token::White,
-            'u' => Token::Blue,
-            'b' => Token::Black,
-            'r' => Token::Red,
-            'g' => Token::Green,
-            _ => panic!(),
-        }
-    }
-}
+use aoc24::towels::{self, Token, Towels};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Puzzle {
-    available: HashSet<Vec<Token>>,
-    max_available_len: usize,
+    towels: Towels,
     needed: Vec<Vec<Token>>,
 }
 
@@ -34,69 +13,68 @@ impl FromStr for Puzzle {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines: Vec<&str> = s.lines().collect();
-        let available: HashSet<Vec<Token>> = lines[0]
-            .split(", ")
-            .map(|s| s.chars().map(Token::from).collect())
-            .collect();
-        let max_available_len = available
+        let towels = lines.first().ok_or(())?.parse()?;
+        let needed = lines
+            .get(2..)
+            .ok_or(())?
             .iter()
-            .map(|a| a.len())
-            .fold(0, |a, len| a.max(len));
-        let needed = lines[2..]
-            .iter()
-            .map(|line| line.chars().map(Token::from).collect())
-            .collect();
-        Ok(Puzzle {
-            available,
-            max_available_len,
-            needed,
-        })
+            .map(|line| towels::parse_tokens(line))
+            .collect::<Option<_>>()
+            .ok_or(())?;
+        Ok(Puzzle { towels, needed })
     }
 }
 
 impl Puzzle {
-    fn process(&self) -> usize {
-        self.needed
-            .iter()
-            .map(|needed| self.count_solutions(needed))
-            .sum()
-    }
-    fn count_solutions(&self, needed: &[Token]) -> usize {
-        let mut count_map = vec![0_usize; needed.len() + 1];
-        count_map[0] = 1;
-        for i in 0..needed.len() {
-            let count = count_map[i];
-            if count == 0 {
-                continue;
-            }
-            for len in 1..=self.max_available_len {
-                if i + len > needed.len() {
-                    continue;
-                }
-                if !self.available.contains(&needed[i..i + len]) {
-                    continue;
-                }
-                count_map[i + len] += count;
-            }
-        }
-        *count_map.last().unwrap()
+    /// Ways to make every design, None if there are too many to count
+    fn process(&self) -> Option<usize> {
+        self.needed.iter().try_fold(0usize, |total, needed| {
+            total.checked_add(self.towels.count_arrangements(needed)?)
+        })
     }
 }
 
 fn main() {
+    logging::init();
     let puzzle = include_str!("19.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process();
-    println!("{out}");
-    // assert_eq!(out, );
+    let out = puzzle.process().unwrap();
+    println!("{}", Answer::new("19b", out));
+    assert_eq!(out, 577474410989846);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
+    /// A design with more arrangements than fit in a `usize`
+    const LONG: &str = "r, rr
+
+rrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrr
+";
+
     #[test]
     fn test() {
         let out = include_str!("19_test.txt").parse::<Puzzle>().unwrap();
         let out = out.process();
-        assert_eq!(out, 16);
+        assert_eq!(out, Some(16));
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day19(&mut Rng::new(19), 12, 20);
+        let puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        let out = LONG.parse::<Puzzle>().unwrap().process();
+        assert_eq!(out, None);
+        let seeds = [include_str!("19_test.txt"), LONG];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            if let Ok(puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
     }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, grid, logging};
 use ndarray::prelude::*;
 use std::str::FromStr;
 
@@ -45,34 +46,27 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let nrows = s.lines().count();
-        let ncols = s.lines().next().unwrap().chars().count();
-        let mut map = Vec::with_capacity(nrows * ncols);
-        let mut start = [0, 0];
-        let mut end = [0, 0];
-        for (irow, line) in s.lines().enumerate() {
-            for (icol, c) in line.chars().enumerate() {
-                map.push(match c {
-                    '.' => Token::Track,
-                    '#' => Token::Wall,
-                    'S' => {
-                        start = [irow, icol];
-                        Token::Track
-                    }
-                    'E' => {
-                        end = [irow, icol];
-                        Token::Track
-                    }
-                    _ => panic!(),
-                });
+        let mut start = None;
+        let mut end = None;
+        let map = grid::parse(s, |pos, c| match c {
+            '.' => Some(Token::Track),
+            '#' => Some(Token::Wall),
+            'S' if start.is_none() => {
+                start = Some(pos);
+                Some(Token::Track)
             }
-        }
-        let map = Array2::from_shape_vec((nrows, ncols), map).unwrap();
+            'E' if end.is_none() => {
+                end = Some(pos);
+                Some(Token::Track)
+            }
+            _ => None,
+        })
+        .ok_or(())?;
         let steps = Array2::from_elem(map.raw_dim(), None);
         Ok(Puzzle {
             map,
-            start,
-            end,
+            start: start.ok_or(())?,
+            end: end.ok_or(())?,
             steps_map: steps,
             positions: Vec::new(),
             cheats: Vec::new(),
@@ -88,18 +82,20 @@ const DIRECTIONS: [Direction; 4] = [
 ];
 
 impl Puzzle {
-    fn solve_steps(&mut self) {
+    /// None unless the track is a single path from start to end
+    fn solve_steps(&mut self) -> Option<()> {
         self.steps_map.iter_mut().for_each(|x| *x = None);
         self.steps_map[self.start] = Some(0);
         let mut pos = self.start;
         let mut steps = 0;
         self.positions.push(pos);
         while pos != self.end {
-            pos = self.find_next_pos(&pos);
+            pos = self.find_next_pos(&pos)?;
             self.positions.push(pos);
             steps += 1;
             self.steps_map[pos] = Some(steps);
         }
+        Some(())
     }
     fn find_cheats(&mut self) {
         let radius = 20_i64;
@@ -130,22 +126,22 @@ impl Puzzle {
                     if cheat_steps > current_steps + cheat_length {
                         let improvement = cheat_steps - (current_steps + cheat_length);
                         self.cheats.push(improvement);
-                        // println!(
-                        //     "Find Cheat {} -> {} , Improvement: {}",
-                        //     current_steps, cheat_steps, improvement
-                        // );
+                        log::trace!(
+                            "cheat {current_steps} -> {cheat_steps}, improvement {improvement}"
+                        );
                     }
                 }
             }
         }
     }
-    fn process(&mut self, time_saved: usize) -> usize {
-        self.solve_steps();
-        self.print_steps();
+    fn process(&mut self, time_saved: usize) -> Option<usize> {
+        self.solve_steps()?;
+        log::debug!("steps\n{}", self.draw_steps());
         self.find_cheats();
-        self.cheats.iter().filter(|&x| x >= &time_saved).count()
+        Some(self.cheats.iter().filter(|&x| x >= &time_saved).count())
     }
-    fn find_next_pos(&self, pos: &[usize; 2]) -> [usize; 2] {
+    /// The only way on, None at a fork or a dead end
+    fn find_next_pos(&self, pos: &[usize; 2]) -> Option<[usize; 2]> {
         let mut next_pos = None;
         for next_direction in DIRECTIONS {
             let potential_next_pos = next_direction.position_from(pos);
@@ -164,31 +160,33 @@ impl Puzzle {
                 continue;
             }
             if next_pos.is_some() {
-                panic!();
+                return None;
             }
             next_pos = Some(potential_next_pos);
         }
-        next_pos.unwrap()
+        next_pos
     }
-    fn print_steps(&self) {
-        for irow in 0..self.steps_map.shape()[0] {
-            for icol in 0..self.steps_map.shape()[1] {
-                if let Some(steps) = self.steps_map[[irow, icol]] {
-                    print!("{:02} ", steps);
-                } else {
-                    print!("   ");
+    /// Steps from the start to each track position, two digits each
+    fn draw_steps(&self) -> String {
+        let mut out = String::new();
+        for row in self.steps_map.rows() {
+            for steps in row {
+                match steps {
+                    Some(steps) => out.push_str(&format!("{:02} ", steps)),
+                    None => out.push_str("   "),
                 }
             }
-            println!();
+            out.push('\n');
         }
-        println!();
+        out
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("20.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(100);
-    println!("{out}");
+    let out = puzzle.process(100).unwrap();
+    println!("{}", Answer::new("20b", out));
     assert_eq!(out, 985482);
 }
 
@@ -197,15 +195,18 @@ mod tests {
     use counter::Counter;
 
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let mut out = include_str!("20_test.txt").parse::<Puzzle>().unwrap();
-        out.solve_steps();
-        out.print_steps();
+        out.solve_steps().unwrap();
+        log::debug!("steps\n{}", out.draw_steps());
         out.find_cheats();
-        let cheat_count = out.cheats.iter().map(|x| *x).collect::<Counter<usize>>();
+        let cheat_count = out.cheats.iter().copied().collect::<Counter<usize>>();
 
-        dbg!(&cheat_count);
+        log::debug!("{:?}", cheat_count);
         assert_eq!(32, cheat_count[&50]);
         assert_eq!(31, cheat_count[&52]);
         assert_eq!(29, cheat_count[&54]);
@@ -224,7 +225,21 @@ mod tests {
     #[test]
     fn test_solve_steps() {
         let mut out = include_str!("20_test.txt").parse::<Puzzle>().unwrap();
-        out.solve_steps();
-        // dbg!(out);
+        out.solve_steps().unwrap();
+        log::debug!("{:?}", out);
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day20(&mut Rng::new(20), [15, 15]);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process(10).is_some());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("20_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process(50);
+            }
+        });
     }
 }
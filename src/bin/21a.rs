@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use ndarray::prelude::*;
 use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
@@ -25,21 +26,23 @@ pub enum DirectionalButton {
     Activate,
 }
 
-impl From<char> for NumericButton {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for NumericButton {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '0' => NumericButton::Zero,
-            '1' => NumericButton::One,
-            '2' => NumericButton::Two,
-            '3' => NumericButton::Three,
-            '4' => NumericButton::Four,
-            '5' => NumericButton::Five,
-            '6' => NumericButton::Six,
-            '7' => NumericButton::Seven,
-            '8' => NumericButton::Eight,
-            '9' => NumericButton::Nine,
-            'A' => NumericButton::Activate,
-            _ => panic!(),
+            '0' => Ok(NumericButton::Zero),
+            '1' => Ok(NumericButton::One),
+            '2' => Ok(NumericButton::Two),
+            '3' => Ok(NumericButton::Three),
+            '4' => Ok(NumericButton::Four),
+            '5' => Ok(NumericButton::Five),
+            '6' => Ok(NumericButton::Six),
+            '7' => Ok(NumericButton::Seven),
+            '8' => Ok(NumericButton::Eight),
+            '9' => Ok(NumericButton::Nine),
+            'A' => Ok(NumericButton::Activate),
+            _ => Err(()),
         }
     }
 }
@@ -282,17 +285,21 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let numeric_sequences = s
-            .lines()
-            .map(|line| line.chars().map(|c| c.into()).collect())
-            .collect();
-        let numbers = s
-            .lines()
-            .map(|line| {
-                let len = line.len();
-                line[0..len - 1].parse::<usize>().unwrap()
-            })
-            .collect();
+        let mut numeric_sequences = Vec::new();
+        let mut numbers = Vec::new();
+        for line in s.lines() {
+            // codes are three digits then A
+            let digits = line.strip_suffix('A').ok_or(())?;
+            if digits.len() != 3 || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(());
+            }
+            numeric_sequences.push(
+                line.chars()
+                    .map(NumericButton::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+            numbers.push(digits.parse::<usize>().map_err(|_| ())?);
+        }
         let numeric_routes = NumericButton::find_routes();
         let directional_routes = DirectionalButton::find_routes();
         Ok(Puzzle {
@@ -339,17 +346,9 @@ impl Display for NumericButton {
         }
     }
 }
-pub fn print_directional_sequence(seq: &[DirectionalButton]) {
-    for button in seq {
-        print!("{}", button);
-    }
-    println!();
-}
-pub fn print_numeric_sequence(seq: &[NumericButton]) {
-    for button in seq {
-        print!("{}", button);
-    }
-    println!();
+/// Buttons as one string like `<vA>^A`
+pub fn format_sequence<T: Display>(seq: &[T]) -> String {
+    seq.iter().map(|button| button.to_string()).collect()
 }
 
 impl Puzzle {
@@ -357,9 +356,13 @@ impl Puzzle {
         let mut out = 0;
         for (sequence, number) in self.numeric_sequences.iter().zip(self.numbers.iter()) {
             let shortest = self.map_sequence(sequence);
-            print_numeric_sequence(sequence);
-            print_directional_sequence(&shortest);
-            println!("{} * {}", number, shortest.len());
+            log::debug!(
+                "{} -> {} : {} * {}",
+                format_sequence(sequence),
+                format_sequence(&shortest),
+                number,
+                shortest.len()
+            );
             out += shortest.len() * number;
         }
         out
@@ -420,15 +423,18 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("21.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("21a", out));
     assert_eq!(out, 231564);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test() {
         let mut out = include_str!("21_test.txt").parse::<Puzzle>().unwrap();
@@ -438,15 +444,20 @@ mod tests {
     fn test_path_finder() {
         let mut solver = PathSolver::new(NumericButton::layout());
         let paths = solver.shortest_paths(&NumericButton::Activate, &NumericButton::Seven);
-        dbg!(paths);
+        log::debug!("{paths:?}");
+        assert!(!paths.is_empty());
     }
     #[test]
     fn test_path_cache_numeric_button() {
-        dbg!(NumericButton::find_routes());
+        let routes = NumericButton::find_routes();
+        log::debug!("{routes:?}");
+        assert!(!routes.is_empty());
     }
     #[test]
     fn test_path_cache_directional_button() {
-        dbg!(DirectionalButton::find_routes());
+        let routes = DirectionalButton::find_routes();
+        log::debug!("{routes:?}");
+        assert!(!routes.is_empty());
     }
     #[test]
     fn test_shortest_seq() {
@@ -460,4 +471,12 @@ mod tests {
             .join("");
         assert_eq!(out.len(), expected.len());
     }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("21_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use memoize::memoize;
 use ndarray::prelude::*;
 use std::{collections::BTreeMap, fmt::Display, str::FromStr};
@@ -26,21 +27,23 @@ pub enum DirectionalButton {
     Activate,
 }
 
-impl From<char> for NumericButton {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for NumericButton {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '0' => NumericButton::Zero,
-            '1' => NumericButton::One,
-            '2' => NumericButton::Two,
-            '3' => NumericButton::Three,
-            '4' => NumericButton::Four,
-            '5' => NumericButton::Five,
-            '6' => NumericButton::Six,
-            '7' => NumericButton::Seven,
-            '8' => NumericButton::Eight,
-            '9' => NumericButton::Nine,
-            'A' => NumericButton::Activate,
-            _ => panic!(),
+            '0' => Ok(NumericButton::Zero),
+            '1' => Ok(NumericButton::One),
+            '2' => Ok(NumericButton::Two),
+            '3' => Ok(NumericButton::Three),
+            '4' => Ok(NumericButton::Four),
+            '5' => Ok(NumericButton::Five),
+            '6' => Ok(NumericButton::Six),
+            '7' => Ok(NumericButton::Seven),
+            '8' => Ok(NumericButton::Eight),
+            '9' => Ok(NumericButton::Nine),
+            'A' => Ok(NumericButton::Activate),
+            _ => Err(()),
         }
     }
 }
@@ -296,17 +299,21 @@ impl FromStr for Puzzle {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let numeric_sequences = s
-            .lines()
-            .map(|line| line.chars().map(|c| c.into()).collect())
-            .collect();
-        let numbers = s
-            .lines()
-            .map(|line| {
-                let len = line.len();
-                line[0..len - 1].parse::<usize>().unwrap()
-            })
-            .collect();
+        let mut numeric_sequences = Vec::new();
+        let mut numbers = Vec::new();
+        for line in s.lines() {
+            // codes are three digits then A
+            let digits = line.strip_suffix('A').ok_or(())?;
+            if digits.len() != 3 || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return Err(());
+            }
+            numeric_sequences.push(
+                line.chars()
+                    .map(NumericButton::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+            numbers.push(digits.parse::<usize>().map_err(|_| ())?);
+        }
         let numeric_routes = NumericButton::find_routes();
         Ok(Puzzle {
             numeric_sequences,
@@ -351,17 +358,9 @@ impl Display for NumericButton {
         }
     }
 }
-pub fn print_directional_sequence(seq: &[DirectionalButton]) {
-    for button in seq {
-        print!("{}", button);
-    }
-    println!();
-}
-pub fn print_numeric_sequence(seq: &[NumericButton]) {
-    for button in seq {
-        print!("{}", button);
-    }
-    println!();
+/// Buttons as one string like `<vA>^A`
+pub fn format_sequence<T: Display>(seq: &[T]) -> String {
+    seq.iter().map(|button| button.to_string()).collect()
 }
 
 #[memoize]
@@ -395,7 +394,7 @@ impl Puzzle {
         let mut out = 0;
         for (sequence, number) in self.numeric_sequences.iter().zip(self.numbers.iter()) {
             let shortest = self.map_sequence(sequence, levels);
-            println!("{} * {}", number, shortest);
+            log::debug!("{} : {} * {}", format_sequence(sequence), number, shortest);
             out += shortest * number;
         }
         out
@@ -429,15 +428,19 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("21.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process(25);
-    println!("{out}");
+    println!("{}", Answer::new("21b", out));
     assert_eq!(out, 281212077733592);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let mut out = include_str!("21_test.txt").parse::<Puzzle>().unwrap();
@@ -447,14 +450,33 @@ mod tests {
     fn test_path_finder() {
         let mut solver = PathSolver::new(NumericButton::layout());
         let paths = solver.shortest_paths(&NumericButton::Activate, &NumericButton::Seven);
-        dbg!(paths);
+        log::debug!("{paths:?}");
+        assert!(!paths.is_empty());
     }
     #[test]
     fn test_path_cache_numeric_button() {
-        dbg!(NumericButton::find_routes());
+        let routes = NumericButton::find_routes();
+        log::debug!("{routes:?}");
+        assert!(!routes.is_empty());
     }
     #[test]
     fn test_path_cache_directional_button() {
-        dbg!(DirectionalButton::find_routes());
+        let routes = DirectionalButton::find_routes();
+        log::debug!("{routes:?}");
+        assert!(!routes.is_empty());
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day21(&mut Rng::new(21), 5);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process(2) > 0);
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("21_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process(2);
+            }
+        });
     }
 }
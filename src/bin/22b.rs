@@ -1,7 +1,6 @@
-use std::{
-    collections::{btree_map::Entry, BTreeMap, BTreeSet, VecDeque},
-    str::FromStr,
-};
+use aoc24::market::{Secret, SequenceTotals, BITS};
+use aoc24::{answer::Answer, logging};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Puzzle {
@@ -14,133 +13,62 @@ impl FromStr for Puzzle {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let initials = s
             .lines()
-            .map(|line| Secret {
-                value: line.parse::<usize>().unwrap(),
+            .map(|line| {
+                let value = line.parse::<usize>().map_err(|_| ())?;
+                // secrets only ever keep 24 bits
+                (value < 1 << BITS).then_some(Secret { value }).ok_or(())
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
         Ok(Puzzle { secrets: initials })
     }
 }
 
-#[derive(Debug)]
-struct Secret {
-    value: usize,
-}
-impl Secret {
-    fn mix(&mut self, num: usize) {
-        self.value ^= num;
-    }
-    fn prune(&mut self) {
-        self.value %= 16777216;
-    }
-    fn evolve(&mut self) {
-        self.mix(self.value * 64);
-        self.prune();
-        self.mix(self.value / 32);
-        self.prune();
-        self.mix(self.value * 2048);
-        self.prune();
-    }
-    fn make_sequence(&mut self, steps: usize) -> BTreeMap<[i8; 4], i8> {
-        let mut out = BTreeMap::new();
-        let mut prev = (self.value % 10) as i8;
-        let mut deltas = VecDeque::new();
-        for _ in 0..steps {
-            self.evolve();
-            let current = (self.value % 10) as i8;
-            deltas.push_back(current - prev);
-            if deltas.len() > 4 {
-                deltas.pop_front();
-            }
-            prev = current;
-            if deltas.len() < 4 {
-                continue;
-            }
-            match out.entry([deltas[0], deltas[1], deltas[2], deltas[3]]) {
-                Entry::Vacant(vacant_entry) => {
-                    vacant_entry.insert(current);
-                }
-                Entry::Occupied(_occupied_entry) => (),
-            }
-        }
-        out
-    }
-}
-
 impl Puzzle {
-    fn process(&mut self, steps: usize) -> usize {
-        let sequences: Vec<_> = self
-            .secrets
-            .iter_mut()
-            .map(|s| s.make_sequence(steps))
-            .collect();
-        let mut keys = BTreeSet::new();
-        for seq in &sequences {
-            for key in seq.keys() {
-                keys.insert(key);
-            }
-        }
-        let mut best_sum = 0;
-        let mut best_key = None;
-        for key in &keys {
-            let sum = sequences
-                .iter()
-                .map(|seq| *seq.get(*key).unwrap_or(&0) as usize)
-                .sum::<usize>();
-            if sum > best_sum {
-                best_sum = sum;
-                best_key = Some(key);
-            }
-        }
-        dbg!(best_key);
-        best_sum
+    /// Best run of price changes and the bananas it earns
+    fn process(&mut self, steps: usize) -> ([i8; 4], usize) {
+        SequenceTotals::new(&self.secrets, steps).best()
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("22.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(2000);
-    println!("{out}");
-    // assert_eq!(out, );
+    let (sequence, out) = puzzle.process(2000);
+    println!("{}", Answer::new("22b", out).with("sequence", sequence));
+    assert_eq!(out, 2089);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let mut out = include_str!("22_test_b.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
-        let out = out.process(2000);
+        log::debug!("{:?}", out);
+        let (sequence, out) = out.process(2000);
+        assert_eq!(sequence, [-2, 1, -1, 3]);
         assert_eq!(out, 23);
     }
     #[test]
-    fn test_mix() {
-        let mut secret = Secret { value: 42 };
-        secret.mix(15);
-        assert_eq!(secret.value, 37);
-    }
-    #[test]
-    fn test_prune() {
-        let mut secret = Secret { value: 100000000 };
-        secret.prune();
-        assert_eq!(secret.value, 16113920);
+    fn test_generated() {
+        let input = generate::day22(&mut Rng::new(22), 10);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process(2000).1 > 0);
     }
     #[test]
-    fn test_evolve() {
-        let mut secret = Secret { value: 123 };
-        let expected = vec![
-            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
-            5908254,
-        ];
-        for expected in expected {
-            secret.evolve();
-            assert_eq!(secret.value, expected);
-        }
-    }
-    #[test]
-    fn test_sequence() {
-        let mut secret = Secret { value: 123 };
-        assert_eq!(secret.make_sequence(10)[&[-1, -1, 0, 2]], 6);
+    fn test_fuzz() {
+        fuzz::fuzz(
+            0,
+            &[include_str!("22_test.txt"), include_str!("22_test_b.txt")],
+            300,
+            |s| {
+                if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                    puzzle.process(2000);
+                }
+            },
+        );
     }
 }
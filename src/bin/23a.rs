@@ -1,9 +1,6 @@
-use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
-    str::FromStr,
-};
-
-use itertools::Itertools;
+use aoc24::graph::{Graph, MAX_NODES};
+use aoc24::{answer::Answer, logging};
+use std::{collections::HashSet, str::FromStr};
 
 #[derive(Debug)]
 struct Connection {
@@ -14,7 +11,7 @@ impl FromStr for Connection {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (a, b) = s.split_once("-").unwrap();
+        let (a, b) = s.split_once("-").ok_or(())?;
         Ok(Connection {
             nodes: [a.into(), b.into()],
         })
@@ -32,65 +29,71 @@ impl FromStr for Puzzle {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let connections = s
             .lines()
-            .map(|line| line.parse::<Connection>().unwrap())
-            .collect();
+            .map(|line| line.parse::<Connection>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let names = connections
+            .iter()
+            .flat_map(|c| &c.nodes)
+            .collect::<HashSet<_>>();
+        if names.len() > MAX_NODES {
+            return Err(());
+        }
         Ok(Puzzle { connections })
     }
 }
 
 impl Puzzle {
+    fn make_graph(&self) -> Graph<'_> {
+        Graph::new(
+            self.connections
+                .iter()
+                .map(|c| (c.nodes[0].as_str(), c.nodes[1].as_str())),
+        )
+        .expect("node count checked when parsing")
+    }
     fn process(&mut self) -> usize {
-        let mut connection_map: HashMap<&str, HashSet<&str>> = HashMap::new();
-        for connection in &self.connections {
-            let source: &str = &connection.nodes[0];
-            let dest: &str = &connection.nodes[1];
-            let mut insert_connection = |source, dest| match connection_map.entry(source) {
-                Entry::Occupied(mut occupied_entry) => {
-                    (*occupied_entry.get_mut()).insert(dest);
-                }
-                Entry::Vacant(vacant_entry) => {
-                    vacant_entry.insert(HashSet::from([dest]));
-                }
-            };
-            insert_connection(source, dest);
-            insert_connection(dest, source);
-        }
-        let mut trios: HashSet<[&str; 3]> = HashSet::new();
-        for (&node, connections) in &connection_map {
-            for others in connections.iter().combinations(2) {
-                if !connection_map[others[0]].contains(others[1]) {
-                    continue;
-                }
-                let mut trio = [node, *others[0], *others[1]];
-                trio.sort();
-                if trios.contains(&trio) {
-                    continue;
-                }
-                trios.insert(trio);
-            }
-        }
-        trios
+        let graph = self.make_graph();
+        graph
+            .cliques(3)
             .iter()
-            .filter(|trio| trio.iter().any(|node| node.starts_with("t")))
+            .filter(|trio| trio.iter().any(|&node| graph.name(node).starts_with("t")))
             .count()
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("23.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("23a", out));
     assert_eq!(out, 1083);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+
     #[test]
     fn test() {
         let mut out = include_str!("23_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
+        log::debug!("{:?}", out);
         let out = out.process();
         assert_eq!(out, 7);
     }
+    #[test]
+    fn test_too_many_nodes() {
+        let s = (0..MAX_NODES)
+            .map(|i| format!("{i}-x\n"))
+            .collect::<String>();
+        assert!(s.parse::<Puzzle>().is_err());
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("23_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
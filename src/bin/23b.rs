@@ -1,9 +1,6 @@
-use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
-    str::FromStr,
-};
-
-use itertools::Itertools;
+use aoc24::graph::{Graph, MAX_NODES};
+use aoc24::{answer::Answer, logging};
+use std::{collections::HashSet, str::FromStr};
 
 #[derive(Debug)]
 struct Connection {
@@ -14,7 +11,7 @@ impl FromStr for Connection {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (a, b) = s.split_once("-").unwrap();
+        let (a, b) = s.split_once("-").ok_or(())?;
         Ok(Connection {
             nodes: [a.into(), b.into()],
         })
@@ -32,80 +29,85 @@ impl FromStr for Puzzle {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let connections = s
             .lines()
-            .map(|line| line.parse::<Connection>().unwrap())
-            .collect();
+            .map(|line| line.parse::<Connection>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let names = connections
+            .iter()
+            .flat_map(|c| &c.nodes)
+            .collect::<HashSet<_>>();
+        if names.len() > MAX_NODES {
+            return Err(());
+        }
         Ok(Puzzle { connections })
     }
 }
 
 impl Puzzle {
-    fn make_connection_map(&self) -> HashMap<&str, HashSet<&str>> {
-        let mut connection_map: HashMap<&str, HashSet<&str>> = HashMap::new();
-        for connection in &self.connections {
-            let source: &str = &connection.nodes[0];
-            let dest: &str = &connection.nodes[1];
-            let mut insert_connection = |source, dest| match connection_map.entry(source) {
-                Entry::Occupied(mut occupied_entry) => {
-                    (*occupied_entry.get_mut()).insert(dest);
-                }
-                Entry::Vacant(vacant_entry) => {
-                    vacant_entry.insert(HashSet::from([dest]));
-                }
-            };
-            insert_connection(source, dest);
-            insert_connection(dest, source);
-        }
-        connection_map
+    fn make_graph(&self) -> Graph<'_> {
+        Graph::new(
+            self.connections
+                .iter()
+                .map(|c| (c.nodes[0].as_str(), c.nodes[1].as_str())),
+        )
+        .expect("node count checked when parsing")
     }
-    fn process(&mut self, size: usize) -> Option<String> {
-        let connection_map = self.make_connection_map();
-        for (key, connections) in &connection_map {
-            'combo: for combination in connections.iter().combinations(size - 1) {
-                for connection in combination.iter().combinations(2) {
-                    if !connection_map[**connection[0]].contains(**connection[1]) {
-                        continue 'combo;
-                    }
-                }
-                let mut nodes: Vec<&str> = Vec::with_capacity(size);
-                nodes.push(key);
-                combination.iter().map(|x| **x).for_each(|x| nodes.push(x));
-                nodes.sort();
-                return Some(nodes.join(","));
-            }
-        }
-        None
+    fn process(&mut self) -> String {
+        let graph = self.make_graph();
+        graph.names(&graph.maximum_clique()).join(",")
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("23.txt").parse::<Puzzle>().unwrap();
-    let out = puzzle.process(13).unwrap();
-    println!("{out}");
+    let out = puzzle.process();
+    println!("{}", Answer::new("23b", out.as_str()));
     assert_eq!(out, "as,bu,cp,dj,ez,fd,hu,it,kj,nx,pp,xh,yu");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let mut out = include_str!("23_test.txt").parse::<Puzzle>().unwrap();
-        dbg!(&out);
-        let out = out.process(4).unwrap();
+        log::debug!("{:?}", out);
+        let out = out.process();
         assert_eq!(out, "co,de,ka,ta");
     }
     #[test]
     fn test_connections() {
         let out = include_str!("23.txt").parse::<Puzzle>().unwrap();
-        for (num, (_key, connections)) in out.make_connection_map().iter().enumerate() {
-            if num % 10 == 0 {
-                print!("{:03}: ", num);
-            }
-            print!("{}, ", connections.len());
-            if num % 10 == 9 {
-                println!()
-            }
+        let graph = out.make_graph();
+        let counts = (0..graph.len())
+            .map(|num| graph.neighbours(num as u16).len())
+            .collect::<Vec<_>>();
+        for (row, counts) in counts.chunks(10).enumerate() {
+            log::debug!("{:03}: {:?}", row * 10, counts);
         }
-        println!()
+    }
+    #[test]
+    fn test_too_many_nodes() {
+        let s = (0..MAX_NODES)
+            .map(|i| format!("{i}-x\n"))
+            .collect::<String>();
+        assert!(s.parse::<Puzzle>().is_err());
+    }
+    #[test]
+    fn test_generated() {
+        let input = generate::day23(&mut Rng::new(23), 40, 6, 0.05);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        assert!(puzzle.process().split(',').count() >= 6);
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("23_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
     }
 }
@@ -1,5 +1,6 @@
+use aoc24::{answer::Answer, logging};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     str::FromStr,
 };
 
@@ -26,12 +27,12 @@ impl FromStr for InitialCondition {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (output, value) = s.split_once(": ").unwrap();
+        let (output, value) = s.split_once(": ").ok_or(())?;
         let output = String::from(output);
         let value = match value {
             "0" => false,
             "1" => true,
-            _ => panic!(),
+            _ => return Err(()),
         };
         Ok(InitialCondition { output, value })
     }
@@ -41,17 +42,17 @@ impl FromStr for Gate {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, output) = s.split_once(" -> ").unwrap();
+        let (left, output) = s.split_once(" -> ").ok_or(())?;
         let output = String::from(output);
-        let (input0, right) = left.split_once(" ").unwrap();
-        let (operation, input1) = right.split_once(" ").unwrap();
+        let (input0, right) = left.split_once(" ").ok_or(())?;
+        let (operation, input1) = right.split_once(" ").ok_or(())?;
         let input0 = String::from(input0);
         let input1 = String::from(input1);
         let operation = match operation {
             "XOR" => Operation::Xor,
             "OR" => Operation::Or,
             "AND" => Operation::And,
-            _ => panic!(),
+            _ => return Err(()),
         };
         Ok(Gate {
             inputs: [input0, input1],
@@ -76,15 +77,32 @@ impl FromStr for Puzzle {
             .iter()
             .enumerate()
             .find(|(_i, x)| x.is_empty())
-            .unwrap();
+            .ok_or(())?;
         let initial_conditions = lines[0..empty_i]
             .iter()
-            .map(|line| line.parse::<InitialCondition>().unwrap())
-            .collect();
+            .map(|line| line.parse::<InitialCondition>())
+            .collect::<Result<Vec<_>, _>>()?;
         let gates = lines[(empty_i + 1)..]
             .iter()
-            .map(|x| x.parse::<Gate>().unwrap())
-            .collect();
+            .map(|x| x.parse::<Gate>())
+            .collect::<Result<Vec<_>, _>>()?;
+        // every wire is set once, and z wires are bits of the answer
+        let mut wires = HashSet::new();
+        let outputs = initial_conditions
+            .iter()
+            .map(|ic| &ic.output)
+            .chain(gates.iter().map(|g| &g.output));
+        for wire in outputs {
+            if !wires.insert(wire.as_str()) {
+                return Err(());
+            }
+            if let Some(num) = wire.strip_prefix('z') {
+                let bit = num.parse::<u32>().ok().filter(|&n| n < usize::BITS);
+                if bit.is_none() {
+                    return Err(());
+                }
+            }
+        }
         Ok(Puzzle {
             initial_conditions,
             gates,
@@ -144,19 +162,47 @@ impl Puzzle {
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("24.txt").parse::<Puzzle>().unwrap();
     let out = puzzle.process();
-    println!("{out}");
+    println!("{}", Answer::new("24a", out));
     assert_eq!(out, 56620966442854);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc24::fuzz;
+    use aoc24::generate::{self, Rng};
+
     #[test]
     fn test() {
         let mut out = include_str!("24_test.txt").parse::<Puzzle>().unwrap();
         let out = out.process();
         assert_eq!(out, 2024);
     }
+    #[test]
+    fn test_generated() {
+        // the adder has no swaps, so it adds
+        let input = generate::day24(&mut Rng::new(24), 8, 0);
+        let mut puzzle = input.parse::<Puzzle>().unwrap();
+        let value = |name: char| {
+            puzzle
+                .initial_conditions
+                .iter()
+                .filter(|ic| ic.output.starts_with(name) && ic.value)
+                .map(|ic| 1 << ic.output[1..].parse::<u32>().unwrap())
+                .sum::<usize>()
+        };
+        let sum = value('x') + value('y');
+        assert_eq!(puzzle.process(), sum);
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("24_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
 }
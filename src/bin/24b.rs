@@ -1,3 +1,4 @@
+use aoc24::{answer::Answer, logging};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     fmt::Display,
@@ -41,17 +42,17 @@ impl FromStr for Gate {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (left, output) = s.split_once(" -> ").unwrap();
+        let (left, output) = s.split_once(" -> ").ok_or(())?;
         let output = String::from(output);
-        let (input0, right) = left.split_once(" ").unwrap();
-        let (operation, input1) = right.split_once(" ").unwrap();
+        let (input0, right) = left.split_once(" ").ok_or(())?;
+        let (operation, input1) = right.split_once(" ").ok_or(())?;
         let input0 = String::from(input0);
         let input1 = String::from(input1);
         let operation = match operation {
             "XOR" => Operation::Xor,
             "OR" => Operation::Or,
             "AND" => Operation::And,
-            _ => panic!(),
+            _ => return Err(()),
         };
         Ok(Gate {
             inputs: [input0, input1],
@@ -76,11 +77,11 @@ impl FromStr for Puzzle {
             .iter()
             .enumerate()
             .find(|(_i, x)| x.is_empty())
-            .unwrap();
+            .ok_or(())?;
         let gates = lines[(empty_i + 1)..]
             .iter()
-            .map(|x| x.parse::<Gate>().unwrap())
-            .collect();
+            .map(|x| x.parse::<Gate>())
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(Puzzle {
             gates,
             swapped: Vec::new(),
@@ -122,27 +123,26 @@ impl Puzzle {
                 if gate.inputs[0].contains("00") || gate.inputs[1].contains("00") {
                     match gate.operation {
                         Operation::Or => {
-                            println!("### num input OR operator: {}", gate);
+                            log::error!("num input OR operator: {}", gate);
                             panic!();
                         }
                         Operation::And => match gates.entry(carry.clone()) {
                             Entry::Occupied(_occupied_entry) => panic!(),
                             Entry::Vacant(vacant_entry) => {
                                 vacant_entry.insert(gate);
-                                println!("{} // {}", gate, &carry);
+                                log::debug!("{} // {}", gate, &carry);
                             }
                         },
                         Operation::Xor => match gates.entry(sum.clone()) {
                             Entry::Occupied(_occupied_entry) => panic!(),
                             Entry::Vacant(vacant_entry) => {
                                 vacant_entry.insert(gate);
-                                println!("{} // {}", gate, &sum);
+                                log::debug!("{} // {}", gate, &sum);
                             }
                         },
                     }
                 }
             }
-            println!();
         }
         // find full adders
         for num in 1..=44 {
@@ -156,28 +156,28 @@ impl Puzzle {
                     input_carry_label = Some(&input_carry_gate.output);
                 }
                 None => {
-                    println!("missing {}", input_carry);
+                    log::warn!("missing {}", input_carry);
                 }
             }
             for gate in &self.gates {
                 if gate.inputs[0].contains(&num_string) || gate.inputs[1].contains(&num_string) {
                     match gate.operation {
                         Operation::Or => {
-                            println!("### num input OR operator: {}", gate);
+                            log::error!("num input OR operator: {}", gate);
                             panic!();
                         }
                         Operation::And => match gates.entry(carry_right.clone()) {
                             Entry::Occupied(_occupied_entry) => panic!(),
                             Entry::Vacant(vacant_entry) => {
                                 vacant_entry.insert(gate);
-                                println!("{} // {}", gate, &carry_right);
+                                log::debug!("{} // {}", gate, &carry_right);
                             }
                         },
                         Operation::Xor => match gates.entry(pre_sum.clone()) {
                             Entry::Occupied(_occupied_entry) => panic!(),
                             Entry::Vacant(vacant_entry) => {
                                 vacant_entry.insert(gate);
-                                println!("{} // {}", gate, &pre_sum);
+                                log::debug!("{} // {}", gate, &pre_sum);
                             }
                         },
                     }
@@ -194,11 +194,11 @@ impl Puzzle {
                     if &gate.inputs[0] != input_carry_label.unwrap()
                         && &gate.inputs[1] != input_carry_label.unwrap()
                     {
-                        println!("### Problem detected with gate: {}", gate);
-                        let labeled: HashSet<&Gate> = gates.values().map(|x| *x).collect();
+                        log::error!("problem detected with gate: {}", gate);
+                        let labeled: HashSet<&Gate> = gates.values().copied().collect();
                         for gate in &self.gates {
                             if !labeled.contains(gate) {
-                                println!("{}", gate);
+                                log::error!("unlabeled: {}", gate);
                             }
                         }
                         panic!();
@@ -210,7 +210,7 @@ impl Puzzle {
                                 Entry::Occupied(_occupied_entry) => panic!(),
                                 Entry::Vacant(vacant_entry) => {
                                     vacant_entry.insert(gate);
-                                    println!("{} // {}", gate, &carry_left);
+                                    log::debug!("{} // {}", gate, &carry_left);
                                 }
                             }
                         }
@@ -218,7 +218,7 @@ impl Puzzle {
                             Entry::Occupied(_occupied_entry) => panic!(),
                             Entry::Vacant(vacant_entry) => {
                                 vacant_entry.insert(gate);
-                                println!("{} // {}", gate, &sum);
+                                log::debug!("{} // {}", gate, &sum);
                                 if !gate.output.contains(&num_string) {
                                     swapped.push(&gate.output);
                                 }
@@ -258,7 +258,7 @@ impl Puzzle {
                                 Entry::Occupied(_occupied_entry) => panic!(),
                                 Entry::Vacant(vacant_entry) => {
                                     vacant_entry.insert(gate);
-                                    println!("{} // {}", gate, &carry);
+                                    log::debug!("{} // {}", gate, &carry);
                                 }
                             }
                         }
@@ -266,15 +266,15 @@ impl Puzzle {
                     }
                 }
             }
-            println!();
         }
         for swapped_name in &swapped {
-            println!("swapped: {}", swapped_name);
+            log::warn!("swapped: {}", swapped_name);
         }
     }
 }
 
 fn main() {
+    logging::init();
     let mut puzzle = include_str!("24.txt").parse::<Puzzle>().unwrap();
     puzzle.perform_swap("rts", "z07");
     puzzle.perform_swap("jpj", "z12");
@@ -282,6 +282,21 @@ fn main() {
     puzzle.perform_swap("vvw", "chv");
     puzzle.check();
     let out = puzzle.process();
-    println!("{}", out);
+    println!("{}", Answer::new("24b", out.as_str()));
     assert_eq!(out, "chv,jpj,kgj,rts,vvw,z07,z12,z26");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc24::fuzz;
+
+    #[test]
+    fn test_fuzz() {
+        fuzz::fuzz(0, &[include_str!("24_test.txt")], 300, |s| {
+            if let Ok(mut puzzle) = s.parse::<Puzzle>() {
+                puzzle.process();
+            }
+        });
+    }
+}
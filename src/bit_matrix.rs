@@ -0,0 +1,107 @@
+//! Square matrices over GF(2) for linear maps on up to 64 bits
+//!
+//! Row `i` holds the bits that are XORed together to give output bit `i`
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    rows: Vec<u64>,
+}
+
+impl BitMatrix {
+    pub fn identity(size: usize) -> BitMatrix {
+        assert!(size <= 64);
+        BitMatrix {
+            rows: (0..size).map(|i| 1 << i).collect(),
+        }
+    }
+    /// Matrix of a linear map given what it does to each single bit
+    pub fn from_fn(size: usize, f: impl Fn(u64) -> u64) -> BitMatrix {
+        assert!(size <= 64);
+        let mut rows = vec![0; size];
+        for column in 0..size {
+            let image = f(1 << column);
+            for (i, row) in rows.iter_mut().enumerate() {
+                *row |= (image >> i & 1) << column;
+            }
+        }
+        BitMatrix { rows }
+    }
+    pub fn size(&self) -> usize {
+        self.rows.len()
+    }
+    pub fn apply(&self, value: u64) -> u64 {
+        self.rows.iter().enumerate().fold(0, |out, (i, row)| {
+            out | (((row & value).count_ones() as u64 & 1) << i)
+        })
+    }
+    /// `self` after `other`
+    pub fn mul(&self, other: &BitMatrix) -> BitMatrix {
+        BitMatrix {
+            rows: self
+                .rows
+                .iter()
+                .map(|&row| {
+                    other
+                        .rows
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| row >> j & 1 == 1)
+                        .fold(0, |out, (_, other_row)| out ^ other_row)
+                })
+                .collect(),
+        }
+    }
+    /// Repeated squaring, `n` applications in O(log n) products
+    pub fn pow(&self, mut n: u64) -> BitMatrix {
+        let mut out = BitMatrix::identity(self.size());
+        let mut base = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                out = out.mul(&base);
+            }
+            base = base.mul(&base);
+            n >>= 1;
+        }
+        out
+    }
+    /// Gauss–Jordan elimination, None if the matrix is singular
+    pub fn inverse(&self) -> Option<BitMatrix> {
+        let size = self.size();
+        let mut rows = self.rows.clone();
+        let mut out = BitMatrix::identity(size).rows;
+        for column in 0..size {
+            let pivot = (column..size).find(|&i| rows[i] >> column & 1 == 1)?;
+            rows.swap(column, pivot);
+            out.swap(column, pivot);
+            for i in 0..size {
+                if i != column && rows[i] >> column & 1 == 1 {
+                    rows[i] ^= rows[column];
+                    out[i] ^= out[column];
+                }
+            }
+        }
+        Some(BitMatrix { rows: out })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_fn() {
+        let rotate = |x: u64| (x << 1 | x >> 7) & 0xff;
+        let matrix = BitMatrix::from_fn(8, rotate);
+        assert_eq!(matrix.apply(0b1000_0011), 0b0000_0111);
+        assert_eq!(matrix.pow(8), BitMatrix::identity(8));
+        assert_eq!(matrix.pow(3).apply(1), 8);
+        let inverse = matrix.inverse().unwrap();
+        assert_eq!(inverse.mul(&matrix), BitMatrix::identity(8));
+        assert_eq!(inverse.apply(1), 0b1000_0000);
+    }
+    #[test]
+    fn test_singular() {
+        let matrix = BitMatrix::from_fn(4, |x| x & 0b0111);
+        assert_eq!(matrix.inverse(), None);
+    }
+}
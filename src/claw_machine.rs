@@ -0,0 +1,282 @@
+//! Claw machines for day 13
+//!
+//! Finding how often to press each button is a 2x2 integer linear system.
+//! Everything is done exactly in `i128`, and buttons that move in the same
+//! direction are handled by picking the cheapest of the many solutions.
+//! Values are limited to `MAX_VALUE` so the products can't overflow.
+
+use std::str::FromStr;
+
+/// Largest button move or prize position, far beyond any real machine
+/// while keeping every product in `solve` well inside `i128`
+pub const MAX_VALUE: usize = 1 << 48;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    /// How far buttons A and B move the claw, as `[x, y]`
+    pub buttons: [[usize; 2]; 2],
+    pub prize: [usize; 2],
+}
+
+impl FromStr for Machine {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_pair = |line: Option<&str>, label: &str, separator: char| {
+            let rem = line.ok_or(())?.strip_prefix(label).ok_or(())?;
+            let (x, y) = rem.split_once(", ").ok_or(())?;
+            let parse_num = |s: &str, axis: char| {
+                let (name, num) = s.split_once(separator).ok_or(())?;
+                if name != axis.to_string() {
+                    return Err(());
+                }
+                let num = num.parse::<usize>().map_err(|_| ())?;
+                (num <= MAX_VALUE).then_some(num).ok_or(())
+            };
+            Ok::<_, ()>([parse_num(x, 'X')?, parse_num(y, 'Y')?])
+        };
+        let mut lines = s.lines();
+        let a = parse_pair(lines.next(), "Button A: ", '+')?;
+        let b = parse_pair(lines.next(), "Button B: ", '+')?;
+        let prize = parse_pair(lines.next(), "Prize: ", '=')?;
+        if lines.next().is_some() {
+            return Err(());
+        }
+        Ok(Machine {
+            buttons: [a, b],
+            prize,
+        })
+    }
+}
+
+/// Machines separated by blank lines, None if any fail to parse
+pub fn parse_machines(s: &str) -> Option<Vec<Machine>> {
+    s.trim_end().split("\n\n").map(|m| m.parse().ok()).collect()
+}
+
+/// Greatest common divisor `g` with `a * x + b * y == g`, as `(g, x, y)`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Cheapest non-negative `a, b` with `a * alpha + b * beta == target`
+/// All inputs are non-negative and `alpha` and `beta` aren't both zero
+fn solve_line(alpha: i128, beta: i128, target: i128, costs: [i128; 2]) -> Option<[i128; 2]> {
+    match (alpha, beta) {
+        (0, _) => (target % beta == 0).then_some([0, target / beta]),
+        (_, 0) => (target % alpha == 0).then_some([target / alpha, 0]),
+        _ => {
+            let (g, x, y) = extended_gcd(alpha, beta);
+            if target % g != 0 {
+                return None;
+            }
+            // every solution is (a0 + step_a * t, b0 - step_b * t)
+            let (a0, b0) = (x * (target / g), y * (target / g));
+            let (step_a, step_b) = (beta / g, alpha / g);
+            let t_min = (-a0).div_euclid(step_a) + i128::from((-a0).rem_euclid(step_a) != 0);
+            let t_max = b0.div_euclid(step_b);
+            if t_min > t_max {
+                return None;
+            }
+            // the cost is linear in t so one of the ends is cheapest
+            let slope = costs[0] * step_a - costs[1] * step_b;
+            let t = if slope >= 0 { t_min } else { t_max };
+            Some([a0 + step_a * t, b0 - step_b * t])
+        }
+    }
+}
+
+impl Machine {
+    /// Move the prize further away on both axes, None and left alone if
+    /// that takes it past `MAX_VALUE`
+    pub fn shift_prize(&mut self, offset: usize) -> Option<()> {
+        let [x, y] = self
+            .prize
+            .map(|p| p.checked_add(offset).filter(|&p| p <= MAX_VALUE));
+        self.prize = [x?, y?];
+        Some(())
+    }
+    /// Buttons moving along the same line, so the presses aren't unique
+    pub fn collinear(&self) -> bool {
+        let [[ax, ay], [bx, by]] = self.buttons.map(|v| v.map(|x| x as u128));
+        ax * by == ay * bx
+    }
+    /// Cheapest number of presses of buttons A and B that reaches the
+    /// prize, where `costs` is the tokens per press of each button
+    /// None if it can't be won or any value is above `MAX_VALUE`
+    pub fn solve(&self, costs: [usize; 2]) -> Option<[usize; 2]> {
+        let values = self
+            .buttons
+            .iter()
+            .flatten()
+            .chain(&self.prize)
+            .chain(&costs);
+        if values.copied().any(|v| v > MAX_VALUE) {
+            return None;
+        }
+        let [[ax, ay], [bx, by]] = self.buttons.map(|v| v.map(|x| x as i128));
+        let [px, py] = self.prize.map(|x| x as i128);
+        let costs = costs.map(|c| c as i128);
+        let det = ax * by - bx * ay;
+        let presses = if det != 0 {
+            // Cramer's rule
+            let a = px * by - bx * py;
+            let b = ax * py - px * ay;
+            if a % det != 0 || b % det != 0 {
+                return None;
+            }
+            [a / det, b / det]
+        } else {
+            // everything has to lie on the line through the buttons
+            let [dx, dy] = if ax != 0 || ay != 0 {
+                [ax, ay]
+            } else {
+                [bx, by]
+            };
+            if dx == 0 && dy == 0 {
+                return (px == 0 && py == 0).then_some([0, 0]);
+            }
+            if px * dy != py * dx {
+                return None;
+            }
+            // measure along the axis that moves the most
+            let axis = usize::from(dy > dx);
+            let along = |v: [i128; 2]| v[axis];
+            solve_line(along([ax, ay]), along([bx, by]), along([px, py]), costs)?
+        };
+        if presses.iter().any(|&p| p < 0) {
+            return None;
+        }
+        Some(presses.map(|p| p as usize))
+    }
+    /// Tokens needed to win the prize, if it can be won
+    pub fn cost(&self, costs: [usize; 2]) -> Option<usize> {
+        let [a, b] = self.solve(costs)?;
+        a.checked_mul(costs[0])?
+            .checked_add(b.checked_mul(costs[1])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    fn machine(buttons: [[usize; 2]; 2], prize: [usize; 2]) -> Machine {
+        Machine { buttons, prize }
+    }
+    #[test]
+    fn test_parse() {
+        let machines = parse_machines(include_str!("bin/13_test.txt")).unwrap();
+        assert_eq!(machines.len(), 4);
+        assert_eq!(machines[0], machine([[94, 34], [22, 67]], [8400, 5400]));
+        assert!("Button A: X+1, Y+2".parse::<Machine>().is_err());
+    }
+    #[test]
+    fn test_solve() {
+        let machines = parse_machines(include_str!("bin/13_test.txt")).unwrap();
+        let solutions = machines.iter().map(|m| m.solve([3, 1])).collect::<Vec<_>>();
+        assert_eq!(solutions, [Some([80, 40]), None, Some([38, 86]), None]);
+    }
+    #[test]
+    fn test_large() {
+        let mut machines = parse_machines(include_str!("bin/13_test.txt")).unwrap();
+        machines
+            .iter_mut()
+            .try_for_each(|m| m.shift_prize(10000000000000))
+            .unwrap();
+        let won = machines
+            .iter()
+            .map(|m| m.cost([3, 1]).is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(won, [false, true, false, true]);
+    }
+    #[test]
+    fn test_too_large() {
+        let big = format!(
+            "Button A: X+1, Y+1\nButton B: X+2, Y+3\nPrize: X={}, Y=5",
+            u64::MAX
+        );
+        assert!(big.parse::<Machine>().is_err());
+        let mut m = machine([[1, 1], [2, 3]], [MAX_VALUE, 5]);
+        assert_eq!(m.shift_prize(1), None);
+        assert_eq!(m.prize, [MAX_VALUE, 5]);
+        assert_eq!(m.shift_prize(0), Some(()));
+        let m = machine([[usize::MAX, 1], [1, usize::MAX]], [usize::MAX, usize::MAX]);
+        assert!(!m.collinear());
+        assert_eq!(m.solve([3, 1]), None);
+        let m = machine(
+            [[MAX_VALUE, MAX_VALUE], [1, MAX_VALUE]],
+            [MAX_VALUE, MAX_VALUE],
+        );
+        assert_eq!(m.cost([MAX_VALUE, MAX_VALUE]), Some(MAX_VALUE));
+        assert_eq!(m.solve([3, 1]), Some([1, 0]));
+        let m = machine([[1, 1], [1, 2]], [MAX_VALUE, MAX_VALUE]);
+        assert_eq!(m.cost([MAX_VALUE, 1]), None);
+    }
+    #[test]
+    fn test_collinear() {
+        let m = machine([[3, 6], [1, 2]], [10, 20]);
+        assert!(m.collinear());
+        // B is cheaper per unit of distance
+        assert_eq!(m.solve([4, 1]), Some([0, 10]));
+        // A is cheaper per unit of distance
+        assert_eq!(m.solve([1, 1]), Some([3, 1]));
+        let m = machine([[4, 2], [6, 3]], [10, 5]);
+        assert_eq!(m.solve([3, 1]), Some([1, 1]));
+        let m = machine([[4, 2], [6, 3]], [2, 1]);
+        assert_eq!(m.solve([3, 1]), None);
+        let m = machine([[4, 2], [6, 3]], [10, 6]);
+        assert_eq!(m.solve([3, 1]), None);
+        let m = machine([[0, 0], [2, 2]], [4, 4]);
+        assert_eq!(m.solve([3, 1]), Some([0, 2]));
+    }
+    /// Cheapest presses found by trying every count of A presses
+    fn brute_force(m: &Machine, costs: [usize; 2]) -> Option<[usize; 2]> {
+        let [a, b] = m.buttons;
+        (0..=m.prize[0].max(m.prize[1]))
+            .filter_map(|na| {
+                let rest = [
+                    m.prize[0].checked_sub(na * a[0])?,
+                    m.prize[1].checked_sub(na * a[1])?,
+                ];
+                let nb = (0..=rest[0].max(rest[1]))
+                    .find(|nb| nb * b[0] == rest[0] && nb * b[1] == rest[1])?;
+                Some([na, nb])
+            })
+            .min_by_key(|&[na, nb]| (na * costs[0] + nb * costs[1], na))
+    }
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(13);
+        let machines = parse_machines(&generate::day13(&mut rng, 30, 0.5)).unwrap();
+        for m in &machines {
+            assert_eq!(m.solve([3, 1]).is_some(), brute_force(m, [3, 1]).is_some());
+            assert_eq!(
+                m.cost([3, 1]),
+                brute_force(m, [3, 1]).map(|[a, b]| 3 * a + b)
+            );
+        }
+        // buttons in the same direction have many solutions to pick from
+        for _ in 0..100 {
+            let direction = [rng.between(0, 4), rng.between(1, 4)];
+            let [ka, kb, kp] = [rng.between(0, 6), rng.between(1, 6), rng.between(0, 40)];
+            let m = machine(
+                [direction.map(|d| d * ka), direction.map(|d| d * kb)],
+                direction.map(|d| d * kp),
+            );
+            let costs = [rng.between(1, 5), rng.between(1, 5)];
+            let cost = |p: [usize; 2]| p[0] * costs[0] + p[1] * costs[1];
+            assert_eq!(
+                m.solve(costs).map(cost),
+                brute_force(&m, costs).map(cost),
+                "{m:?}"
+            );
+        }
+    }
+}
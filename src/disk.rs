@@ -0,0 +1,243 @@
+//! Disk map for day 9, stored as runs of file and free blocks
+
+use std::{cmp::Reverse, collections::BinaryHeap, fmt::Display, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    /// Extents of each file, indexed by file id
+    files: Vec<Vec<Span>>,
+    /// Free spans in disk order, only valid before compaction
+    free: Vec<Span>,
+    size: usize,
+}
+
+impl FromStr for DiskMap {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut files = Vec::new();
+        let mut free = Vec::new();
+        let mut start = 0;
+        for (i, c) in s.trim().chars().enumerate() {
+            let len = c.to_digit(10).ok_or(())? as usize;
+            if i % 2 == 0 {
+                files.push(if len > 0 {
+                    vec![Span { start, len }]
+                } else {
+                    Vec::new()
+                });
+            } else if len > 0 {
+                free.push(Span { start, len });
+            }
+            start += len;
+        }
+        Ok(DiskMap {
+            files,
+            free,
+            size: start,
+        })
+    }
+}
+
+impl DiskMap {
+    pub fn files(&self) -> &[Vec<Span>] {
+        &self.files
+    }
+    /// Move single blocks from the end of the disk into the leftmost free block
+    /// Files get fragmented into several extents
+    pub fn compact_blocks(&mut self) {
+        let mut free = std::mem::take(&mut self.free).into_iter();
+        let mut gap = free.next();
+        for id in (0..self.files.len()).rev() {
+            // a file that was not moved has exactly one extent
+            while let (Some(space), Some(&last)) = (gap.as_mut(), self.files[id].last()) {
+                if space.start >= last.start {
+                    return;
+                }
+                let moved = space.len.min(last.len);
+                let extents = &mut self.files[id];
+                extents.last_mut().unwrap().len -= moved;
+                if extents.last().unwrap().len == 0 {
+                    extents.pop();
+                }
+                extents.insert(
+                    0,
+                    Span {
+                        start: space.start,
+                        len: moved,
+                    },
+                );
+                space.start += moved;
+                space.len -= moved;
+                if space.len == 0 {
+                    gap = free.next();
+                }
+                if moved == last.len {
+                    break;
+                }
+            }
+            if gap.is_none() {
+                return;
+            }
+        }
+    }
+    /// Move whole files, highest id first, into the leftmost free span that fits
+    /// Each size keeps a min-heap of free span starts so a lookup costs
+    /// one heap peek per size
+    pub fn compact_files(&mut self) {
+        let max_len = self.free.iter().map(|s| s.len).max().unwrap_or(0);
+        let mut heaps = vec![BinaryHeap::new(); max_len + 1];
+        for span in std::mem::take(&mut self.free) {
+            heaps[span.len].push(Reverse(span.start));
+        }
+        for id in (0..self.files.len()).rev() {
+            let Some(&file) = self.files[id].first() else {
+                continue;
+            };
+            let best = (file.len..=max_len)
+                .filter_map(|len| heaps[len].peek().map(|&Reverse(start)| (start, len)))
+                .filter(|&(start, _)| start < file.start)
+                .min();
+            let Some((start, len)) = best else {
+                continue;
+            };
+            heaps[len].pop();
+            if len > file.len {
+                heaps[len - file.len].push(Reverse(start + file.len));
+            }
+            self.files[id] = vec![Span {
+                start,
+                len: file.len,
+            }];
+        }
+    }
+    pub fn checksum(&self) -> usize {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(|(id, extents)| extents.iter().map(move |span| (id, span)))
+            .map(|(id, span)| id * (span.len * span.start + span.len * (span.len - 1) / 2))
+            .sum()
+    }
+}
+
+/// Block layout such as `00...111...2...`, ids above 9 wrap around
+impl Display for DiskMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut blocks = vec!['.'; self.size];
+        for (id, extents) in self.files.iter().enumerate() {
+            let digit = char::from_digit((id % 10) as u32, 10).unwrap();
+            for span in extents {
+                blocks[span.start..span.start + span.len].fill(digit);
+            }
+        }
+        write!(f, "{}", blocks.into_iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    /// One entry per block, the file id or None if free
+    fn blocks(s: &str) -> Vec<Option<usize>> {
+        let mut out = Vec::new();
+        for (i, c) in s.trim().chars().enumerate() {
+            let id = (i % 2 == 0).then_some(i / 2);
+            out.extend(std::iter::repeat_n(id, c.to_digit(10).unwrap() as usize));
+        }
+        out
+    }
+    fn block_checksum(blocks: &[Option<usize>]) -> usize {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, id)| i * id.unwrap_or(0))
+            .sum()
+    }
+
+    #[test]
+    fn test_render() {
+        let disk = "12345".parse::<DiskMap>().unwrap();
+        assert_eq!(disk.to_string(), "0..111....22222");
+    }
+    #[test]
+    fn test_compact_blocks() {
+        let mut disk = "12345".parse::<DiskMap>().unwrap();
+        disk.compact_blocks();
+        assert_eq!(disk.to_string(), "022111222......");
+        let mut disk = "2333133121414131402".parse::<DiskMap>().unwrap();
+        disk.compact_blocks();
+        assert_eq!(
+            disk.to_string(),
+            "0099811188827773336446555566.............."
+        );
+        assert_eq!(disk.checksum(), 1928);
+    }
+    #[test]
+    fn test_compact_files() {
+        let mut disk = "2333133121414131402".parse::<DiskMap>().unwrap();
+        disk.compact_files();
+        assert_eq!(
+            disk.to_string(),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+        assert_eq!(disk.checksum(), 2858);
+    }
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(9);
+        for _ in 0..100 {
+            let files = rng.between(1, 40);
+            let input = generate::day09(&mut rng, files);
+            // blocks: swap the first gap with the last file block
+            let mut expected = blocks(&input);
+            let (mut left, mut right) = (0, expected.len() - 1);
+            while left < right {
+                if expected[left].is_some() {
+                    left += 1;
+                } else if expected[right].is_none() {
+                    right -= 1;
+                } else {
+                    expected.swap(left, right);
+                }
+            }
+            let mut disk = input.parse::<DiskMap>().unwrap();
+            disk.compact_blocks();
+            assert_eq!(disk.checksum(), block_checksum(&expected), "{input}");
+            // files: scan from the left for the first gap that fits
+            let mut expected = blocks(&input);
+            for id in (0..files).rev() {
+                let start = expected.iter().position(|&b| b == Some(id)).unwrap();
+                let len = expected.iter().filter(|&&b| b == Some(id)).count();
+                let gap = (0..start).find(|&i| expected[i..i + len].iter().all(|b| b.is_none()));
+                if let Some(gap) = gap {
+                    expected[start..start + len].fill(None);
+                    expected[gap..gap + len].fill(Some(id));
+                }
+            }
+            let mut disk = input.parse::<DiskMap>().unwrap();
+            disk.compact_files();
+            assert_eq!(disk.checksum(), block_checksum(&expected), "{input}");
+            // moving whole files never packs tighter than moving blocks
+            let mut fragmented = input.parse::<DiskMap>().unwrap();
+            fragmented.compact_blocks();
+            let sizes = |disk: &DiskMap| {
+                disk.files()
+                    .iter()
+                    .map(|f| f.iter().map(|s| s.len).sum::<usize>())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(sizes(&fragmented), sizes(&disk));
+            let end = |disk: &DiskMap| disk.files().iter().flatten().map(|s| s.start + s.len).max();
+            assert!(end(&fragmented) <= end(&disk));
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Operator equations for day 7
+//!
+//! Operators are evaluated strictly left to right. The solver works
+//! backwards from the result, undoing the last operator at each step, and
+//! only searches forwards where an operator cannot be undone uniquely.
+
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct Equation {
+    pub result: usize,
+    pub values: Vec<usize>,
+}
+
+impl FromStr for Equation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (result, values) = s.split_once(": ").ok_or(())?;
+        let result = result.parse().map_err(|_| ())?;
+        let values = values
+            .split(" ")
+            .map(|x| x.parse::<usize>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(());
+        }
+        Ok(Equation { result, values })
+    }
+}
+
+/// Left operands that give a result for a known right operand
+#[derive(Debug, PartialEq, Eq)]
+pub enum Inverse {
+    /// No left operand works
+    Impossible,
+    Unique(usize),
+    /// Several left operands might work, search forwards instead
+    Ambiguous,
+}
+
+pub trait Operator: Sync + Send {
+    fn symbol(&self) -> &'static str;
+    /// None if the value is out of range
+    fn apply(&self, left: usize, right: usize) -> Option<usize>;
+    fn invert(&self, _result: usize, _right: usize) -> Inverse {
+        Inverse::Ambiguous
+    }
+}
+
+pub struct Add;
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+    fn apply(&self, left: usize, right: usize) -> Option<usize> {
+        left.checked_add(right)
+    }
+    fn invert(&self, result: usize, right: usize) -> Inverse {
+        result
+            .checked_sub(right)
+            .map_or(Inverse::Impossible, Inverse::Unique)
+    }
+}
+
+pub struct Multiply;
+impl Operator for Multiply {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+    fn apply(&self, left: usize, right: usize) -> Option<usize> {
+        left.checked_mul(right)
+    }
+    fn invert(&self, result: usize, right: usize) -> Inverse {
+        match (result, right) {
+            (0, 0) => Inverse::Ambiguous,
+            (_, 0) => Inverse::Impossible,
+            _ if result.is_multiple_of(right) => Inverse::Unique(result / right),
+            _ => Inverse::Impossible,
+        }
+    }
+}
+
+/// Digits of `right` appended to `left`
+pub struct Concatenate;
+impl Concatenate {
+    /// Power of ten above `right`, None if that doesn't fit in usize
+    fn shift(right: usize) -> Option<usize> {
+        let mut value: usize = 10;
+        while value <= right {
+            value = value.checked_mul(10)?;
+        }
+        Some(value)
+    }
+}
+impl Operator for Concatenate {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
+    fn apply(&self, left: usize, right: usize) -> Option<usize> {
+        left.checked_mul(Concatenate::shift(right)?)?
+            .checked_add(right)
+    }
+    fn invert(&self, result: usize, right: usize) -> Inverse {
+        let Some(shift) = Concatenate::shift(right) else {
+            return Inverse::Impossible;
+        };
+        if result % shift == right {
+            Inverse::Unique(result / shift)
+        } else {
+            Inverse::Impossible
+        }
+    }
+}
+
+pub struct Subtract;
+impl Operator for Subtract {
+    fn symbol(&self) -> &'static str {
+        "-"
+    }
+    fn apply(&self, left: usize, right: usize) -> Option<usize> {
+        left.checked_sub(right)
+    }
+    fn invert(&self, result: usize, right: usize) -> Inverse {
+        result
+            .checked_add(right)
+            .map_or(Inverse::Impossible, Inverse::Unique)
+    }
+}
+
+pub struct Xor;
+impl Operator for Xor {
+    fn symbol(&self) -> &'static str {
+        "^"
+    }
+    fn apply(&self, left: usize, right: usize) -> Option<usize> {
+        Some(left ^ right)
+    }
+    fn invert(&self, result: usize, right: usize) -> Inverse {
+        Inverse::Unique(result ^ right)
+    }
+}
+
+pub struct Solver {
+    operators: Vec<Box<dyn Operator>>,
+}
+
+impl Solver {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Solver {
+        Solver { operators }
+    }
+    pub fn solvable(&self, equation: &Equation) -> bool {
+        self.solve(equation).is_some()
+    }
+    /// Operator indices for one way to make the equation true
+    pub fn solve(&self, equation: &Equation) -> Option<Vec<usize>> {
+        let mut out = None;
+        self.search(equation, &mut |ops| {
+            out = Some(ops.to_vec());
+            false
+        });
+        out
+    }
+    pub fn count_solutions(&self, equation: &Equation) -> usize {
+        let mut out = 0;
+        self.search(equation, &mut |_| {
+            out += 1;
+            true
+        });
+        out
+    }
+    /// Write out a solution such as `3267 = 81 + 40 * 27`
+    pub fn explain(&self, equation: &Equation, operators: &[usize]) -> String {
+        let mut out = format!("{} = {}", equation.result, equation.values[0]);
+        for (op, value) in operators.iter().zip(&equation.values[1..]) {
+            out += &format!(" {} {}", self.operators[*op].symbol(), value);
+        }
+        out
+    }
+    /// Call `found` with each solution until it returns false
+    fn search(&self, equation: &Equation, found: &mut dyn FnMut(&[usize]) -> bool) {
+        let mut ops = vec![0; equation.values.len() - 1];
+        self.backward(&equation.values, equation.result, &mut ops, found);
+    }
+    /// Undo the last operator of `values` from `target`
+    /// Returns false once `found` asks to stop
+    fn backward(
+        &self,
+        values: &[usize],
+        target: usize,
+        ops: &mut [usize],
+        found: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        let last = values.len() - 1;
+        if last == 0 {
+            return values[0] != target || found(ops);
+        }
+        for (index, operator) in self.operators.iter().enumerate() {
+            ops[last - 1] = index;
+            let keep_going = match operator.invert(target, values[last]) {
+                Inverse::Impossible => true,
+                Inverse::Unique(left) => self.backward(&values[..last], left, ops, found),
+                Inverse::Ambiguous => {
+                    self.forward(&values[..last], 1, values[0], ops, &mut |ops, prefix| {
+                        operator.apply(prefix, values[last]) != Some(target) || found(ops)
+                    })
+                }
+            };
+            if !keep_going {
+                return false;
+            }
+        }
+        true
+    }
+    /// Try every operator sequence on `values` starting from `partial`
+    /// and hand each final value to `found`
+    fn forward(
+        &self,
+        values: &[usize],
+        position: usize,
+        partial: usize,
+        ops: &mut [usize],
+        found: &mut dyn FnMut(&[usize], usize) -> bool,
+    ) -> bool {
+        if position == values.len() {
+            return found(ops, partial);
+        }
+        for (index, operator) in self.operators.iter().enumerate() {
+            let Some(partial) = operator.apply(partial, values[position]) else {
+                continue;
+            };
+            ops[position - 1] = index;
+            if !self.forward(values, position + 1, partial, ops, found) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day7() -> Solver {
+        Solver::new(vec![
+            Box::new(Add),
+            Box::new(Multiply),
+            Box::new(Concatenate),
+        ])
+    }
+    #[test]
+    fn test_solve() {
+        let solver = day7();
+        let equation = "3267: 81 40 27".parse::<Equation>().unwrap();
+        assert_eq!(solver.count_solutions(&equation), 2);
+        let ops = solver.solve(&equation).unwrap();
+        assert_eq!(solver.explain(&equation, &ops), "3267 = 81 * 40 + 27");
+        let equation = "7290: 6 8 6 15".parse::<Equation>().unwrap();
+        let ops = solver.solve(&equation).unwrap();
+        assert_eq!(solver.explain(&equation, &ops), "7290 = 6 * 8 || 6 * 15");
+        let equation = "83: 17 5".parse::<Equation>().unwrap();
+        assert!(!solver.solvable(&equation));
+    }
+    #[test]
+    fn test_other_operators() {
+        let solver = Solver::new(vec![Box::new(Subtract), Box::new(Xor)]);
+        let equation = "6: 10 3 1".parse::<Equation>().unwrap();
+        assert_eq!(solver.count_solutions(&equation), 2);
+        let ops = solver.solve(&equation).unwrap();
+        assert_eq!(solver.explain(&equation, &ops), "6 = 10 - 3 - 1");
+    }
+    #[test]
+    fn test_ambiguous() {
+        // 0 * 0 can't be undone, so the prefix is searched forwards
+        let solver = Solver::new(vec![Box::new(Add), Box::new(Multiply)]);
+        let equation = "0: 2 2 0 0".parse::<Equation>().unwrap();
+        assert_eq!(solver.count_solutions(&equation), 6);
+    }
+    #[test]
+    fn test_concatenate_overflow() {
+        let big = 10_000_000_000_000_000_000;
+        assert_eq!(Concatenate.apply(5, big), None);
+        assert_eq!(Concatenate.apply(0, usize::MAX), None);
+        assert_eq!(Concatenate.invert(10, big), Inverse::Impossible);
+        let equation = "10: 5 10000000000000000000".parse::<Equation>().unwrap();
+        assert!(!day7().solvable(&equation));
+    }
+}
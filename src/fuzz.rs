@@ -0,0 +1,148 @@
+//! Mutation fuzzing for the puzzle parsers, run offline as plain tests
+//!
+//! Inputs are made by breaking up a known good input a few bytes or lines
+//! at a time, so most of them get some way into the parser before going
+//! wrong. Any panic fails with the input that caused it. Runs are a fixed
+//! number of inputs, so they don't depend on the machine; set
+//! `AOC_FUZZ_TIME_LIMIT` to a number of seconds to also fail on any input
+//! that takes longer.
+
+use crate::generate::Rng;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+pub const TIME_LIMIT_VARIABLE: &str = "AOC_FUZZ_TIME_LIMIT";
+
+/// Longest one input may take, None unless `AOC_FUZZ_TIME_LIMIT` is set
+pub fn time_limit() -> Option<Duration> {
+    let seconds = std::env::var(TIME_LIMIT_VARIABLE).ok()?;
+    seconds.trim().parse().ok().map(Duration::from_secs_f64)
+}
+
+/// Bytes that mean something to at least one parser
+const INTERESTING: &[u8] = b"0123456789 \n,:|-+=#.^<>vSEOAXMxyz@";
+
+/// Numbers at the edges of what the parsers accept
+const NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "255",
+    "65536",
+    "4294967296",
+    "18446744073709551615",
+    "18446744073709551616",
+    "99999999999999999999999",
+];
+
+/// A copy of `input` with one to four random changes
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    for _ in 0..rng.between(1, 4) {
+        let len = bytes.len();
+        let at = rng.below(len + 1);
+        let end = (at + rng.between(1, 16)).min(len);
+        match rng.below(8) {
+            0 if at < len => bytes[at] = *rng.pick(INTERESTING),
+            1 if at < len => bytes[at] = rng.below(256) as u8,
+            2 => {
+                bytes.drain(at..end);
+            }
+            3 => {
+                let copy = bytes[at..end].to_vec();
+                bytes.splice(at..at, copy);
+            }
+            4 => {
+                bytes.splice(at..at, rng.pick(NUMBERS).bytes());
+            }
+            5 => bytes.truncate(at),
+            6 => {
+                // swap two lines
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                let mut lines = text.lines().collect::<Vec<_>>();
+                if !lines.is_empty() {
+                    let (i, j) = (rng.below(lines.len()), rng.below(lines.len()));
+                    lines.swap(i, j);
+                }
+                bytes = (lines.join("\n") + "\n").into_bytes();
+            }
+            _ => {
+                // drop or repeat a whole line
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                let mut lines = text.lines().collect::<Vec<_>>();
+                if !lines.is_empty() {
+                    let i = rng.below(lines.len());
+                    if rng.chance(0.5) {
+                        lines.remove(i);
+                    } else {
+                        lines.insert(i, lines[i]);
+                    }
+                }
+                bytes = (lines.join("\n") + "\n").into_bytes();
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Feed `target` the seed inputs, the empty string and `runs` mutations of
+/// the seeds, panicking with the input if it panics or is over `time_limit`
+pub fn fuzz(seed: u64, inputs: &[&str], runs: usize, target: impl Fn(&str)) {
+    let limit = time_limit();
+    let mut rng = Rng::new(seed);
+    let mutations = (0..runs).map(|_| {
+        let input = *rng.pick(inputs);
+        mutate(&mut rng, input)
+    });
+    let cases = inputs
+        .iter()
+        .map(|s| s.to_string())
+        .chain([String::new()])
+        .collect::<Vec<_>>();
+    for input in cases.into_iter().chain(mutations) {
+        let start = Instant::now();
+        if panic::catch_unwind(AssertUnwindSafe(|| target(&input))).is_err() {
+            panic!("panicked on input {input:?}");
+        }
+        if let Some(limit) = limit {
+            let elapsed = start.elapsed();
+            assert!(elapsed < limit, "took {elapsed:?} on input {input:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = Rng::new(1);
+        let mutations = (0..100)
+            .map(|_| mutate(&mut rng, "1,2\n3,4\n"))
+            .collect::<Vec<_>>();
+        assert!(mutations.iter().any(|m| m != "1,2\n3,4\n"));
+        assert!(mutations.iter().any(|m| m.contains("3,4\n1,2")));
+        assert_eq!(
+            mutate(&mut Rng::new(4), "abc"),
+            mutate(&mut Rng::new(4), "abc")
+        );
+    }
+    #[test]
+    fn test_fuzz() {
+        fuzz(1, &["12 34"], 100, |s| {
+            s.split_whitespace().for_each(|n| {
+                let _ = n.parse::<u8>();
+            });
+        });
+        let caught = panic::catch_unwind(|| {
+            fuzz(1, &["12 34"], 100, |s| {
+                s.split_whitespace().for_each(|n| {
+                    n.parse::<u8>().unwrap();
+                });
+            })
+        });
+        assert!(caught.is_err());
+    }
+}
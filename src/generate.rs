@@ -0,0 +1,746 @@
+//! Random puzzle inputs for every day, for stress tests and benchmarks
+//!
+//! Each `dayNN` function writes an input in the same format as `NN.txt`.
+//! The same seed and sizes always give the same input.
+
+use std::collections::BTreeSet;
+
+/// SplitMix64, small and plenty random enough for test inputs
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    /// Uniform in `0..n`, `n` must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+    /// Uniform in `low..=high`
+    pub fn between(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high - low + 1)
+    }
+    /// Uniform in `low..=high`, for signed values
+    pub fn between_signed(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as usize) as i64
+    }
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < p
+    }
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn grid_string(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Random cell strictly inside the border of a `shape` grid
+fn inner_cell(rng: &mut Rng, shape: [usize; 2]) -> [usize; 2] {
+    [rng.between(1, shape[0] - 2), rng.between(1, shape[1] - 2)]
+}
+
+/// Perfect maze carved by a random depth first search, walls on even
+/// rows and columns, then with a fraction `loops` of inner walls removed
+/// `shape` is rounded down to odd sizes of at least 3
+fn maze(rng: &mut Rng, shape: [usize; 2], loops: f64) -> Vec<Vec<char>> {
+    let [nrows, ncols] = shape.map(|x| (x.max(3) - 1) / 2 * 2 + 1);
+    let mut grid = vec![vec!['#'; ncols]; nrows];
+    let mut stack = vec![[1, 1]];
+    grid[1][1] = '.';
+    while let Some(&[row, col]) = stack.last() {
+        let mut options = Vec::new();
+        if row >= 3 && grid[row - 2][col] == '#' {
+            options.push([row - 2, col]);
+        }
+        if row + 2 < nrows && grid[row + 2][col] == '#' {
+            options.push([row + 2, col]);
+        }
+        if col >= 3 && grid[row][col - 2] == '#' {
+            options.push([row, col - 2]);
+        }
+        if col + 2 < ncols && grid[row][col + 2] == '#' {
+            options.push([row, col + 2]);
+        }
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = *rng.pick(&options);
+        grid[(row + next[0]) / 2][(col + next[1]) / 2] = '.';
+        grid[next[0]][next[1]] = '.';
+        stack.push(next);
+    }
+    for (row, line) in grid.iter_mut().enumerate().take(nrows - 1).skip(1) {
+        for (col, cell) in line.iter_mut().enumerate().take(ncols - 1).skip(1) {
+            // walls between two cells in a line
+            let between = (row % 2 == 1) != (col % 2 == 1);
+            if between && *cell == '#' && rng.chance(loops) {
+                *cell = '.';
+            }
+        }
+    }
+    grid
+}
+
+/// Two columns of location ids, with `shared` chance of a right value
+/// repeating one from the left
+pub fn day01(rng: &mut Rng, rows: usize, shared: f64) -> String {
+    let left = (0..rows)
+        .map(|_| rng.between(10000, 99999))
+        .collect::<Vec<_>>();
+    let mut out = String::new();
+    for &a in &left {
+        let b = if rng.chance(shared) {
+            *rng.pick(&left)
+        } else {
+            rng.between(10000, 99999)
+        };
+        out += &format!("{a}   {b}\n");
+    }
+    out
+}
+
+/// Reports that mostly step steadily up or down, some with a bad level
+pub fn day02(rng: &mut Rng, reports: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..reports {
+        let len = rng.between(5, 8);
+        let up = rng.chance(0.5);
+        let mut level = rng.between(30, 60) as i64;
+        let mut levels = Vec::with_capacity(len);
+        for _ in 0..len {
+            levels.push(level);
+            let step = rng.between_signed(1, 3);
+            level += if up { step } else { -step };
+        }
+        for _ in 0..rng.below(3) {
+            let i = rng.below(len);
+            levels[i] += rng.between_signed(-4, 4);
+        }
+        let levels = levels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        out += &(levels.join(" ") + "\n");
+    }
+    out
+}
+
+/// Corrupted memory with `mul(a,b)`, `do()` and `don't()` among noise
+pub fn day03(rng: &mut Rng, len: usize) -> String {
+    let noise = b"xmul()[]{},;:'!@#$%^&*+-<>?~ don't0123456789";
+    let mut out = String::new();
+    while out.len() < len {
+        match rng.below(10) {
+            0..=2 => out += &format!("mul({},{})", rng.between(0, 999), rng.between(0, 999)),
+            3 => out += "do()",
+            4 => out += "don't()",
+            // near misses
+            5 => out += &format!("mul({},{}]", rng.between(0, 999), rng.between(0, 999)),
+            6 => out += &format!("mul ( {},{})", rng.below(10), rng.below(10)),
+            _ => {
+                for _ in 0..rng.between(1, 8) {
+                    out.push(*rng.pick(noise) as char);
+                }
+            }
+        }
+    }
+    out + "\n"
+}
+
+/// Letters from XMAS with some XMAS words written in
+pub fn day04(rng: &mut Rng, shape: [usize; 2], words: usize) -> String {
+    let letters = ['X', 'M', 'A', 'S'];
+    let mut grid = (0..shape[0])
+        .map(|_| (0..shape[1]).map(|_| *rng.pick(&letters)).collect())
+        .collect::<Vec<Vec<char>>>();
+    for _ in 0..words {
+        let direction = *rng.pick(&crate::word_search::DIRECTIONS);
+        let start = [rng.below(shape[0]) as i64, rng.below(shape[1]) as i64];
+        let end = [start[0] + 3 * direction[0], start[1] + 3 * direction[1]];
+        if end[0] < 0 || end[1] < 0 || end[0] >= shape[0] as i64 || end[1] >= shape[1] as i64 {
+            continue;
+        }
+        for (k, c) in "XMAS".chars().enumerate() {
+            let row = start[0] + k as i64 * direction[0];
+            let col = start[1] + k as i64 * direction[1];
+            grid[row as usize][col as usize] = c;
+        }
+    }
+    grid_string(&grid)
+}
+
+/// Rules for every pair of `pages` pages in one random order, then updates
+/// of odd length, about half already in order
+pub fn day05(rng: &mut Rng, pages: usize, updates: usize) -> String {
+    let pages = pages.clamp(1, 90);
+    let mut order = (10..100).collect::<Vec<usize>>();
+    rng.shuffle(&mut order);
+    order.truncate(pages);
+    let mut rules = Vec::new();
+    for (i, &left) in order.iter().enumerate() {
+        for &right in &order[i + 1..] {
+            rules.push(format!("{left}|{right}\n"));
+        }
+    }
+    rng.shuffle(&mut rules);
+    let mut out = rules.concat() + "\n";
+    for _ in 0..updates {
+        let len = rng.between(0, (pages - 1) / 2) * 2 + 1;
+        let mut indices = (0..pages).collect::<Vec<_>>();
+        rng.shuffle(&mut indices);
+        indices.truncate(len);
+        if rng.chance(0.5) {
+            indices.sort();
+        }
+        let update = indices
+            .iter()
+            .map(|&i| order[i].to_string())
+            .collect::<Vec<_>>();
+        out += &(update.join(",") + "\n");
+    }
+    out
+}
+
+/// Lab map with obstacles at `density` and the guard facing up
+pub fn day06(rng: &mut Rng, shape: [usize; 2], density: f64) -> String {
+    let mut grid = (0..shape[0])
+        .map(|_| {
+            (0..shape[1])
+                .map(|_| if rng.chance(density) { '#' } else { '.' })
+                .collect()
+        })
+        .collect::<Vec<Vec<char>>>();
+    grid[rng.below(shape[0])][rng.below(shape[1])] = '^';
+    grid_string(&grid)
+}
+
+/// Equations built from random operators, about half knocked off by one
+pub fn day07(rng: &mut Rng, equations: usize, max_values: usize) -> String {
+    let mut out = String::new();
+    let mut written = 0;
+    while written < equations {
+        let values = (0..rng.between(2, max_values.max(2)))
+            .map(|_| rng.between(1, 999))
+            .collect::<Vec<_>>();
+        let mut result = Some(values[0]);
+        for &value in &values[1..] {
+            result = result.and_then(|r| match rng.below(3) {
+                0 => r.checked_add(value),
+                1 => r.checked_mul(value),
+                _ => r
+                    .checked_mul(10_usize.pow(value.ilog10() + 1))
+                    .and_then(|r| r.checked_add(value)),
+            });
+        }
+        // keep the sums well inside a usize
+        let Some(result) = result.filter(|&r| r < 1 << 60) else {
+            continue;
+        };
+        let result = if rng.chance(0.5) { result + 1 } else { result };
+        let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        out += &format!("{result}: {}\n", values.join(" "));
+        written += 1;
+    }
+    out
+}
+
+/// Antenna map with `frequencies` different frequencies of a few antennas
+pub fn day08(rng: &mut Rng, shape: [usize; 2], frequencies: usize) -> String {
+    let names = ('0'..='9')
+        .chain('a'..='z')
+        .chain('A'..='Z')
+        .collect::<Vec<_>>();
+    let mut grid = vec![vec!['.'; shape[1]]; shape[0]];
+    for &name in names.iter().take(frequencies) {
+        for _ in 0..rng.between(2, 4) {
+            grid[rng.below(shape[0])][rng.below(shape[1])] = name;
+        }
+    }
+    grid_string(&grid)
+}
+
+/// Disk map of `files` files, file lengths 1-9 and gaps 0-9
+pub fn day09(rng: &mut Rng, files: usize) -> String {
+    let mut out = String::with_capacity(files * 2);
+    for i in 0..files {
+        out.push(char::from(b'0' + rng.between(1, 9) as u8));
+        if i + 1 < files {
+            out.push(char::from(b'0' + rng.between(0, 9) as u8));
+        }
+    }
+    out + "\n"
+}
+
+/// Random heights with `trails` climbing walks from 0 to 9 drawn in
+pub fn day10(rng: &mut Rng, shape: [usize; 2], trails: usize) -> String {
+    let mut grid = (0..shape[0])
+        .map(|_| (0..shape[1]).map(|_| rng.below(10)).collect())
+        .collect::<Vec<Vec<usize>>>();
+    for _ in 0..trails {
+        let mut position = [rng.below(shape[0]), rng.below(shape[1])];
+        for height in 0..10 {
+            grid[position[0]][position[1]] = height;
+            let [row, col] = position;
+            let options = [
+                [row.wrapping_sub(1), col],
+                [row + 1, col],
+                [row, col.wrapping_sub(1)],
+                [row, col + 1],
+            ]
+            .into_iter()
+            .filter(|p| p[0] < shape[0] && p[1] < shape[1])
+            .collect::<Vec<_>>();
+            position = *rng.pick(&options);
+        }
+    }
+    grid.iter()
+        .map(|row| row.iter().map(|h| h.to_string()).collect::<String>() + "\n")
+        .collect()
+}
+
+/// Stones engraved with numbers up to `max`
+pub fn day11(rng: &mut Rng, stones: usize, max: usize) -> String {
+    let stones = (0..stones)
+        .map(|_| rng.between(0, max).to_string())
+        .collect::<Vec<_>>();
+    stones.join(" ") + "\n"
+}
+
+/// Garden plots from `plants` letters, cells copy a neighbour with chance
+/// `clumping` so the regions grow
+pub fn day12(rng: &mut Rng, shape: [usize; 2], plants: usize, clumping: f64) -> String {
+    let letters = ('A'..='Z').take(plants.clamp(1, 26)).collect::<Vec<_>>();
+    let mut grid = vec![vec!['A'; shape[1]]; shape[0]];
+    for row in 0..shape[0] {
+        for col in 0..shape[1] {
+            grid[row][col] = if row > 0 && rng.chance(clumping / 2.0) {
+                grid[row - 1][col]
+            } else if col > 0 && rng.chance(clumping) {
+                grid[row][col - 1]
+            } else {
+                *rng.pick(&letters)
+            };
+        }
+    }
+    grid_string(&grid)
+}
+
+/// Claw machines, with chance `winnable` of a prize reachable in at most
+/// 100 presses of each button
+pub fn day13(rng: &mut Rng, machines: usize, winnable: f64) -> String {
+    let mut blocks = Vec::with_capacity(machines);
+    for _ in 0..machines {
+        let a = [rng.between(10, 99), rng.between(10, 99)];
+        let b = [rng.between(10, 99), rng.between(10, 99)];
+        let prize = if rng.chance(winnable) {
+            let (na, nb) = (rng.between(0, 100), rng.between(0, 100));
+            [na * a[0] + nb * b[0], na * a[1] + nb * b[1]]
+        } else {
+            [rng.between(1000, 20000), rng.between(1000, 20000)]
+        };
+        blocks.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a[0], a[1], b[0], b[1], prize[0], prize[1]
+        ));
+    }
+    blocks.join("\n")
+}
+
+/// Robots anywhere in a `room` sized room moving at most `room` per second
+pub fn day14(rng: &mut Rng, robots: usize, room: [usize; 2]) -> String {
+    let mut out = String::new();
+    for _ in 0..robots {
+        let p = [rng.below(room[0]), rng.below(room[1])];
+        let v = [
+            rng.between_signed(-(room[0] as i64) + 1, room[0] as i64 - 1),
+            rng.between_signed(-(room[1] as i64) + 1, room[1] as i64 - 1),
+        ];
+        out += &format!("p={},{} v={},{}\n", p[0], p[1], v[0], v[1]);
+    }
+    out
+}
+
+/// Walled warehouse with boxes, some inner walls, the robot and its moves
+pub fn day15(rng: &mut Rng, shape: [usize; 2], boxes: f64, moves: usize) -> String {
+    let mut grid = vec![vec!['#'; shape[1]]; shape[0]];
+    for row in grid.iter_mut().take(shape[0] - 1).skip(1) {
+        for cell in row.iter_mut().take(shape[1] - 1).skip(1) {
+            *cell = if rng.chance(boxes) {
+                'O'
+            } else if rng.chance(0.05) {
+                '#'
+            } else {
+                '.'
+            };
+        }
+    }
+    let robot = inner_cell(rng, shape);
+    grid[robot[0]][robot[1]] = '@';
+    let mut out = grid_string(&grid) + "\n";
+    let directions = ['<', '>', '^', 'v'];
+    for i in 0..moves {
+        out.push(*rng.pick(&directions));
+        if i % 70 == 69 || i + 1 == moves {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Reindeer maze with S bottom left and E top right
+pub fn day16(rng: &mut Rng, shape: [usize; 2], loops: f64) -> String {
+    let mut grid = maze(rng, shape, loops);
+    let (nrows, ncols) = (grid.len(), grid[0].len());
+    grid[nrows - 2][1] = 'S';
+    grid[1][ncols - 2] = 'E';
+    grid_string(&grid)
+}
+
+/// 3-bit program shaped like the real ones: a loop that mixes the low
+/// bits of A into B, prints B and shifts A by 3 until it runs out
+pub fn day17(rng: &mut Rng, a_bits: u32) -> String {
+    let a = rng.next_u64() % (1 << a_bits.min(63));
+    let mut body = vec![
+        [1, rng.below(8)],
+        [7, 5],
+        [1, rng.below(8)],
+        [4, rng.below(8)],
+    ];
+    rng.shuffle(&mut body[1..]);
+    let mut program = vec![[2, 4]];
+    program.extend(body);
+    program.insert(rng.between(1, program.len()), [0, 3]);
+    program.extend([[5, 5], [3, 0]]);
+    let program = program
+        .iter()
+        .map(|[op, arg]| format!("{op},{arg}"))
+        .collect::<Vec<_>>();
+    format!(
+        "Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+        program.join(",")
+    )
+}
+
+/// `bytes` distinct falling bytes, never on the start or the exit
+pub fn day18(rng: &mut Rng, size: usize, bytes: usize) -> String {
+    let mut cells = (0..size * size)
+        .map(|i| [i % size, i / size])
+        .filter(|&c| c != [0, 0] && c != [size - 1, size - 1])
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut cells);
+    cells
+        .iter()
+        .take(bytes)
+        .map(|[x, y]| format!("{x},{y}\n"))
+        .collect()
+}
+
+/// Towel patterns, and designs that are about half made from them
+pub fn day19(rng: &mut Rng, patterns: usize, designs: usize) -> String {
+    let colours = ['w', 'u', 'b', 'r', 'g'];
+    let mut towels = BTreeSet::new();
+    while towels.len() < patterns.min(200) {
+        let len = rng.between(1, 8);
+        towels.insert((0..len).map(|_| *rng.pick(&colours)).collect::<String>());
+    }
+    let towels = towels.into_iter().collect::<Vec<_>>();
+    let mut out = towels.join(", ") + "\n\n";
+    for _ in 0..designs {
+        let design = if rng.chance(0.5) {
+            (0..rng.between(1, 10))
+                .map(|_| rng.pick(&towels).as_str())
+                .collect::<String>()
+        } else {
+            (0..rng.between(5, 60))
+                .map(|_| *rng.pick(&colours))
+                .collect::<String>()
+        };
+        out += &(design + "\n");
+    }
+    out
+}
+
+/// Race track with a single path from S to E, everything else wall
+pub fn day20(rng: &mut Rng, shape: [usize; 2]) -> String {
+    let mut grid = maze(rng, shape, 0.0);
+    let (nrows, ncols) = (grid.len(), grid[0].len());
+    let start = [1, 1];
+    let end = [nrows - 2, ncols - 2];
+    // a perfect maze has one path between any two cells, find it by
+    // walking back from the end along the search tree
+    let mut previous = vec![vec![None; ncols]; nrows];
+    let mut queue = std::collections::VecDeque::from([start]);
+    previous[1][1] = Some(start);
+    while let Some([row, col]) = queue.pop_front() {
+        for next in [
+            [row - 1, col],
+            [row + 1, col],
+            [row, col - 1],
+            [row, col + 1],
+        ] {
+            if grid[next[0]][next[1]] == '.' && previous[next[0]][next[1]].is_none() {
+                previous[next[0]][next[1]] = Some([row, col]);
+                queue.push_back(next);
+            }
+        }
+    }
+    let mut track = vec![vec!['#'; ncols]; nrows];
+    let mut position = end;
+    while position != start {
+        track[position[0]][position[1]] = '.';
+        position = previous[position[0]][position[1]].unwrap();
+    }
+    track[start[0]][start[1]] = 'S';
+    track[end[0]][end[1]] = 'E';
+    grid = track;
+    grid_string(&grid)
+}
+
+/// Door codes of three digits then `A`
+pub fn day21(rng: &mut Rng, codes: usize) -> String {
+    (0..codes)
+        .map(|_| format!("{:03}A\n", rng.below(1000)))
+        .collect()
+}
+
+/// Initial secrets, all below 2^24
+pub fn day22(rng: &mut Rng, buyers: usize) -> String {
+    (0..buyers)
+        .map(|_| format!("{}\n", rng.below(1 << 24)))
+        .collect()
+}
+
+/// Network of `computers` computers with a hidden clique of `clique`
+/// and each other pair linked with chance `density`
+pub fn day23(rng: &mut Rng, computers: usize, clique: usize, density: f64) -> String {
+    let mut names = BTreeSet::new();
+    while names.len() < computers.min(26 * 26) {
+        let name = [rng.below(26), rng.below(26)]
+            .map(|c| char::from(b'a' + c as u8))
+            .iter()
+            .collect::<String>();
+        names.insert(name);
+    }
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    let clique = clique.min(names.len());
+    let mut links = Vec::new();
+    for i in 0..names.len() {
+        for j in i + 1..names.len() {
+            if j < clique || rng.chance(density) {
+                let (a, b) = if rng.chance(0.5) { (i, j) } else { (j, i) };
+                links.push(format!("{}-{}\n", names[a], names[b]));
+            }
+        }
+    }
+    rng.shuffle(&mut links);
+    links.concat()
+}
+
+/// Ripple carry adder for `bits` bit inputs with `swaps` pairs of gate
+/// outputs swapped, each pair within a different bit
+pub fn day24(rng: &mut Rng, bits: usize, swaps: usize) -> String {
+    let bits = bits.clamp(2, 99);
+    let mut used = BTreeSet::new();
+    let mut wire = |rng: &mut Rng| loop {
+        let name = [rng.below(23), rng.below(26), rng.below(26)]
+            .map(|c| char::from(b'a' + c as u8))
+            .iter()
+            .collect::<String>();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+    let mut out = String::new();
+    for input in ['x', 'y'] {
+        for bit in 0..bits {
+            out += &format!("{input}{bit:02}: {}\n", rng.below(2));
+        }
+    }
+    out += "\n";
+    // gates by bit, as (input, operation, input, output)
+    let mut gates: Vec<Vec<(String, &str, String, String)>> = Vec::with_capacity(bits);
+    let mut carry = wire(rng);
+    gates.push(vec![
+        ("x00".into(), "XOR", "y00".into(), "z00".into()),
+        ("x00".into(), "AND", "y00".into(), carry.clone()),
+    ]);
+    for bit in 1..bits {
+        let (x, y) = (format!("x{bit:02}"), format!("y{bit:02}"));
+        let (sum, both, pass) = (wire(rng), wire(rng), wire(rng));
+        let next = if bit + 1 == bits {
+            format!("z{bits:02}")
+        } else {
+            wire(rng)
+        };
+        gates.push(vec![
+            (x.clone(), "XOR", y.clone(), sum.clone()),
+            (x, "AND", y, both.clone()),
+            (sum.clone(), "XOR", carry.clone(), format!("z{bit:02}")),
+            (sum, "AND", carry, pass.clone()),
+            (both, "OR", pass, next.clone()),
+        ]);
+        carry = next;
+    }
+    // swaps that can't make a loop: neither gate feeds the other
+    let safe = [[0, 1], [2, 1], [2, 3], [2, 4]];
+    let mut swappable = (1..bits).collect::<Vec<_>>();
+    rng.shuffle(&mut swappable);
+    for &bit in swappable.iter().take(swaps) {
+        let [i, j] = *rng.pick(&safe);
+        let output = gates[bit][i].3.clone();
+        gates[bit][i].3 = std::mem::replace(&mut gates[bit][j].3, output);
+    }
+    let mut lines = gates
+        .into_iter()
+        .flatten()
+        .map(|(a, op, b, output)| {
+            if rng.chance(0.5) {
+                format!("{a} {op} {b} -> {output}\n")
+            } else {
+                format!("{b} {op} {a} -> {output}\n")
+            }
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+    out + &lines.concat()
+}
+
+/// Locks and keys `width` pins wide with `space` rows for the pins
+pub fn day25(rng: &mut Rng, schematics: usize, width: usize, space: usize) -> String {
+    let mut blocks = Vec::with_capacity(schematics);
+    for _ in 0..schematics {
+        let heights = (0..width).map(|_| rng.below(space + 1)).collect::<Vec<_>>();
+        let mut rows = vec![vec!['#'; width]];
+        for row in 0..=space {
+            rows.push(
+                heights
+                    .iter()
+                    .map(|&h| if row < h { '#' } else { '.' })
+                    .collect(),
+            );
+        }
+        if rng.chance(0.5) {
+            // a key is a lock upside down
+            rows.reverse();
+        }
+        blocks.push(grid_string(&rows));
+    }
+    blocks.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        antennas::Antennas, claw_machine, disk::DiskMap, equation::Equation, graph::Graph,
+        instructions::Scanner, location_lists::Lists, page_order::Update, schematics::Schematics,
+        towels::Towels, word_search,
+    };
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(
+            day16(&mut Rng::new(7), [21, 31], 0.1),
+            day16(&mut Rng::new(7), [21, 31], 0.1)
+        );
+        assert_ne!(day22(&mut Rng::new(1), 10), day22(&mut Rng::new(2), 10));
+        let mut rng = Rng::new(3);
+        assert!((0..1000).all(|_| (5..=9).contains(&rng.between(5, 9))));
+    }
+    #[test]
+    fn test_parse() {
+        let rng = &mut Rng::new(2024);
+        let lists = day01(rng, 100, 0.2).parse::<Lists>().unwrap();
+        assert_eq!((lists.columns(), lists.len()), (2, 100));
+        assert_eq!(day02(rng, 10).lines().count(), 10);
+        let pages = day05(rng, 11, 20);
+        let (rules, updates) = pages.split_once("\n\n").unwrap();
+        assert_eq!(rules.lines().count(), 55);
+        assert!(updates.lines().all(|u| u.parse::<Update>().is_ok()));
+        assert!(day07(rng, 20, 6)
+            .lines()
+            .all(|e| e.parse::<Equation>().is_ok()));
+        assert!(day08(rng, [20, 30], 5).parse::<Antennas>().is_ok());
+        let disk = day09(rng, 50).parse::<DiskMap>().unwrap();
+        assert_eq!(disk.files().len(), 50);
+        assert_eq!(
+            claw_machine::parse_machines(&day13(rng, 8, 0.5))
+                .unwrap()
+                .len(),
+            8
+        );
+        let towels = day19(rng, 20, 5);
+        assert!(towels.lines().next().unwrap().parse::<Towels>().is_ok());
+        let network = day23(rng, 40, 6, 0.05);
+        let graph = Graph::new(network.lines().map(|l| l.split_once('-').unwrap())).unwrap();
+        assert!(graph.maximum_clique().len() >= 6);
+        let schematics = day25(rng, 30, 5, 5).parse::<Schematics>().unwrap();
+        assert_eq!(schematics.locks.len() + schematics.keys.len(), 30);
+        // days 3 and 4 parse in the library, the other days in their binaries
+        let memory = day03(rng, 500);
+        assert!(Scanner::day3().scan_enabled(&memory).count() > 0);
+        let grid = word_search::parse_grid(&day04(rng, [10, 12], 5)).unwrap();
+        assert_eq!(grid.dim(), (10, 12));
+        word_search::find_words(grid.view(), &["XMAS"]);
+    }
+    #[test]
+    fn test_grids() {
+        let rng = &mut Rng::new(99);
+        let lab = day06(rng, [10, 12], 0.1);
+        assert_eq!(lab.matches('^').count(), 1);
+        assert!(lab.lines().all(|l| l.len() == 12));
+        let warehouse = day15(rng, [8, 10], 0.2, 100);
+        let (map, moves) = warehouse.split_once("\n\n").unwrap();
+        assert_eq!(map.matches('@').count(), 1);
+        assert_eq!(moves.replace('\n', "").len(), 100);
+        let maze = day16(rng, [15, 20], 0.1);
+        assert_eq!(maze.lines().count(), 15);
+        assert!(maze.lines().all(|l| l.len() == 19));
+        // a race track has no branches, each cell has at most 2 neighbours
+        let track = day20(rng, [15, 15]);
+        let grid = track.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+        for row in 1..grid.len() - 1 {
+            for col in 1..grid[0].len() - 1 {
+                if grid[row][col] != b'#' {
+                    let neighbours = [
+                        grid[row - 1][col],
+                        grid[row + 1][col],
+                        grid[row][col - 1],
+                        grid[row][col + 1],
+                    ];
+                    assert!(neighbours.iter().filter(|&&c| c != b'#').count() <= 2);
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_adder() {
+        let rng = &mut Rng::new(5);
+        let adder = day24(rng, 8, 0);
+        // 8 bits of x and y, 1 half adder and 7 full adders
+        assert_eq!(adder.lines().count(), 16 + 1 + 2 + 7 * 5);
+        assert!(adder.contains("-> z08"));
+        let swapped = day24(&mut Rng::new(5), 8, 2);
+        assert_eq!(swapped.lines().count(), adder.lines().count());
+        assert_ne!(swapped, adder);
+    }
+}
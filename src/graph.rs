@@ -0,0 +1,371 @@
+//! Clique search on undirected graphs such as the day 23 LAN party
+//!
+//! Node names are interned to dense `u16` ids in name order, and each
+//! node's neighbours are a bitset, so set operations work a word at a time
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Most nodes a graph can have, one for each `u16` id
+pub const MAX_NODES: usize = u16::MAX as usize + 1;
+
+/// Set of node ids, one bit per node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSet {
+    words: Vec<u64>,
+}
+
+impl NodeSet {
+    pub fn new(size: usize) -> NodeSet {
+        NodeSet {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+    pub fn insert(&mut self, id: u16) {
+        self.words[id as usize / 64] |= 1 << (id % 64);
+    }
+    pub fn remove(&mut self, id: u16) {
+        self.words[id as usize / 64] &= !(1 << (id % 64));
+    }
+    pub fn contains(&self, id: u16) -> bool {
+        self.words[id as usize / 64] & (1 << (id % 64)) != 0
+    }
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+    pub fn intersection(&self, other: &NodeSet) -> NodeSet {
+        NodeSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+    pub fn difference(&self, other: &NodeSet) -> NodeSet {
+        NodeSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & !b)
+                .collect(),
+        }
+    }
+    pub fn union(&self, other: &NodeSet) -> NodeSet {
+        NodeSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a | b)
+                .collect(),
+        }
+    }
+    pub fn intersection_len(&self, other: &NodeSet) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+    /// Ids in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word &= word - 1;
+                Some((i * 64 + bit as usize) as u16)
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Graph<'a> {
+    /// Node names indexed by id, sorted
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, u16>,
+    adjacency: Vec<NodeSet>,
+}
+
+impl<'a> Graph<'a> {
+    /// Graph of the connections, None if there are more than `MAX_NODES`
+    /// names. A node connected to itself is kept with no self-edge
+    pub fn new(connections: impl IntoIterator<Item = (&'a str, &'a str)>) -> Option<Graph<'a>> {
+        let connections = connections.into_iter().collect::<Vec<_>>();
+        let names = connections
+            .iter()
+            .flat_map(|&(a, b)| [a, b])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if names.len() > MAX_NODES {
+            return None;
+        }
+        let ids: HashMap<&str, u16> = names
+            .iter()
+            .enumerate()
+            .map(|(id, &name)| (name, id as u16))
+            .collect();
+        let mut adjacency = vec![NodeSet::new(names.len()); names.len()];
+        for (a, b) in connections.into_iter().filter(|(a, b)| a != b) {
+            let (a, b) = (ids[a], ids[b]);
+            adjacency[a as usize].insert(b);
+            adjacency[b as usize].insert(a);
+        }
+        Some(Graph {
+            names,
+            ids,
+            adjacency,
+        })
+    }
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+    pub fn id(&self, name: &str) -> Option<u16> {
+        self.ids.get(name).copied()
+    }
+    pub fn name(&self, id: u16) -> &'a str {
+        self.names[id as usize]
+    }
+    /// Names for a list of ids, in the same order
+    pub fn names(&self, ids: &[u16]) -> Vec<&'a str> {
+        ids.iter().map(|&id| self.name(id)).collect()
+    }
+    pub fn neighbours(&self, id: u16) -> &NodeSet {
+        &self.adjacency[id as usize]
+    }
+    fn all(&self) -> NodeSet {
+        let mut out = NodeSet::new(self.len());
+        (0..self.len()).for_each(|id| out.insert(id as u16));
+        out
+    }
+    /// Repeatedly remove a node of smallest remaining degree
+    /// Ties go to the smallest id so the order is deterministic
+    fn degeneracy_order(&self) -> Vec<u16> {
+        let mut degrees = self.adjacency.iter().map(|n| n.len()).collect::<Vec<_>>();
+        let mut queue: BTreeSet<(usize, u16)> = degrees
+            .iter()
+            .enumerate()
+            .map(|(id, &d)| (d, id as u16))
+            .collect();
+        let mut remaining = self.all();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some((_, node)) = queue.pop_first() {
+            remaining.remove(node);
+            for neighbour in self.neighbours(node).intersection(&remaining).iter() {
+                let degree = &mut degrees[neighbour as usize];
+                queue.remove(&(*degree, neighbour));
+                *degree -= 1;
+                queue.insert((*degree, neighbour));
+            }
+            order.push(node);
+        }
+        order
+    }
+    /// Bron–Kerbosch with pivoting
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<u16>,
+        mut candidates: NodeSet,
+        mut excluded: NodeSet,
+        out: &mut Vec<Vec<u16>>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut clique = clique.clone();
+                clique.sort();
+                out.push(clique);
+            }
+            return;
+        }
+        let pivot = candidates
+            .union(&excluded)
+            .iter()
+            .max_by_key(|&u| self.neighbours(u).intersection_len(&candidates))
+            .unwrap();
+        for node in candidates.difference(self.neighbours(pivot)).iter() {
+            let neighbours = self.neighbours(node);
+            clique.push(node);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbours),
+                excluded.intersection(neighbours),
+                out,
+            );
+            clique.pop();
+            candidates.remove(node);
+            excluded.insert(node);
+        }
+    }
+    /// Every clique that cannot be extended, each sorted by id
+    pub fn maximal_cliques(&self) -> Vec<Vec<u16>> {
+        let mut later = self.all();
+        let mut earlier = NodeSet::new(self.len());
+        let mut out = Vec::new();
+        for node in self.degeneracy_order() {
+            later.remove(node);
+            let neighbours = self.neighbours(node);
+            self.bron_kerbosch(
+                &mut vec![node],
+                later.intersection(neighbours),
+                earlier.intersection(neighbours),
+                &mut out,
+            );
+            earlier.insert(node);
+        }
+        out.sort();
+        out
+    }
+    /// Largest clique, ties broken by the smallest ids
+    pub fn maximum_clique(&self) -> Vec<u16> {
+        self.maximal_cliques()
+            .into_iter()
+            .min_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)))
+            .unwrap_or_default()
+    }
+    /// Extend `clique` with `size` more nodes taken from `candidates`
+    /// Only larger ids are added so each clique is built once
+    fn extend_cliques(
+        &self,
+        clique: &mut Vec<u16>,
+        candidates: &NodeSet,
+        size: usize,
+        out: &mut Vec<Vec<u16>>,
+    ) {
+        if size == 0 {
+            out.push(clique.clone());
+            return;
+        }
+        let mut remaining = candidates.clone();
+        for node in candidates.iter() {
+            remaining.remove(node);
+            let next = remaining.intersection(self.neighbours(node));
+            if next.len() + 1 < size {
+                continue;
+            }
+            clique.push(node);
+            self.extend_cliques(clique, &next, size - 1, out);
+            clique.pop();
+        }
+    }
+    /// All cliques with exactly `size` nodes, each sorted by id
+    pub fn cliques(&self, size: usize) -> Vec<Vec<u16>> {
+        let mut out = Vec::new();
+        self.extend_cliques(&mut Vec::with_capacity(size), &self.all(), size, &mut out);
+        out
+    }
+    pub fn count_cliques(&self, size: usize) -> usize {
+        self.cliques(size).len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph<'static> {
+        Graph::new(
+            include_str!("bin/23_test.txt")
+                .lines()
+                .map(|line| line.split_once("-").unwrap()),
+        )
+        .unwrap()
+    }
+    #[test]
+    fn test_interner() {
+        let graph = example();
+        assert_eq!(graph.len(), 16);
+        assert_eq!(graph.id("aq"), Some(0));
+        assert_eq!(graph.id("zz"), None);
+        assert_eq!(graph.names(&[0, 1]), ["aq", "cg"]);
+        let ka = graph.id("ka").unwrap();
+        let neighbours = graph.names(&graph.neighbours(ka).iter().collect::<Vec<_>>());
+        assert_eq!(neighbours, ["co", "de", "ta", "tb"]);
+    }
+    #[test]
+    fn test_node_set() {
+        let mut a = NodeSet::new(130);
+        let mut b = NodeSet::new(130);
+        [1, 64, 65, 129].into_iter().for_each(|id| a.insert(id));
+        [0, 64, 129].into_iter().for_each(|id| b.insert(id));
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), [64, 129]);
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [1, 65]);
+        assert_eq!(a.union(&b).len(), 5);
+        assert_eq!(a.intersection_len(&b), 2);
+        a.remove(64);
+        assert!(!a.contains(64));
+    }
+    #[test]
+    fn test_maximum_clique() {
+        let graph = example();
+        assert_eq!(
+            graph.names(&graph.maximum_clique()),
+            ["co", "de", "ka", "ta"]
+        );
+    }
+    #[test]
+    fn test_count_cliques() {
+        let graph = example();
+        assert_eq!(graph.count_cliques(1), 16);
+        assert_eq!(graph.count_cliques(2), 32);
+        assert_eq!(graph.count_cliques(3), 12);
+        assert_eq!(graph.count_cliques(4), 1);
+        assert_eq!(graph.count_cliques(5), 0);
+    }
+    #[test]
+    fn test_self_loops() {
+        let graph = Graph::new([
+            ("aa", "aa"),
+            ("aa", "bb"),
+            ("bb", "bb"),
+            ("bb", "cc"),
+            ("cc", "cc"),
+            ("cc", "aa"),
+            ("dd", "dd"),
+        ])
+        .unwrap();
+        assert!(!graph.neighbours(0).contains(0));
+        assert_eq!(graph.cliques(3), [[0, 1, 2]]);
+        assert_eq!(graph.maximum_clique(), [0, 1, 2]);
+        assert_eq!(graph.maximal_cliques(), [vec![0, 1, 2], vec![3]]);
+    }
+    #[test]
+    fn test_too_many_nodes() {
+        let names = (0..MAX_NODES).map(|i| i.to_string()).collect::<Vec<_>>();
+        assert!(Graph::new(names.iter().map(|n| (n.as_str(), "x"))).is_none());
+    }
+    #[test]
+    fn test_maximal_cliques() {
+        let graph = example();
+        let cliques = graph.maximal_cliques();
+        // every maximal clique is a clique and cannot be grown
+        for clique in &cliques {
+            for (i, &a) in clique.iter().enumerate() {
+                for &b in &clique[i + 1..] {
+                    assert!(graph.neighbours(a).contains(b));
+                }
+            }
+            assert!((0..graph.len() as u16)
+                .filter(|n| !clique.contains(n))
+                .all(|n| clique.iter().any(|&c| !graph.neighbours(c).contains(n))));
+        }
+        // every triangle is inside some maximal clique
+        for trio in graph.cliques(3) {
+            assert!(cliques
+                .iter()
+                .any(|clique| trio.iter().all(|n| clique.contains(n))));
+        }
+    }
+}
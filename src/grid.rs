@@ -0,0 +1,45 @@
+//! Reading rectangular grids with one cell per character
+
+use ndarray::prelude::*;
+
+/// Grid of `cell(position, char)` for every character, None if there are
+/// no lines, the lines aren't all the same length or `cell` gives None
+pub fn parse<T>(s: &str, mut cell: impl FnMut([usize; 2], char) -> Option<T>) -> Option<Array2<T>> {
+    let nrows = s.lines().count();
+    let ncols = s.lines().next()?.chars().count();
+    let mut cells = Vec::with_capacity(nrows * ncols);
+    for (irow, line) in s.lines().enumerate() {
+        if line.chars().count() != ncols {
+            return None;
+        }
+        for (icol, c) in line.chars().enumerate() {
+            cells.push(cell([irow, icol], c)?);
+        }
+    }
+    Array2::from_shape_vec((nrows, ncols), cells).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = parse("12\n34\n", |_, c| c.to_digit(10)).unwrap();
+        assert_eq!(grid, array![[1, 2], [3, 4]]);
+        let mut seen = Vec::new();
+        parse("ab\ncd", |pos, c| {
+            seen.push((pos, c));
+            Some(())
+        });
+        assert_eq!(seen[2], ([1, 0], 'c'));
+        assert!(parse("12\n3x", |_, c| c.to_digit(10)).is_none());
+    }
+    #[test]
+    fn test_ragged() {
+        assert!(parse("..\n..^\n.", |_, c| Some(c)).is_none());
+        assert!(parse("...\n..", |_, c| Some(c)).is_none());
+        assert!(parse("", |_, c| Some(c)).is_none());
+        assert_eq!(parse("é.\n..", |_, c| Some(c)).unwrap().dim(), (2, 2));
+    }
+}
@@ -0,0 +1,234 @@
+//! Single pass scanner for instructions hidden in corrupted memory (day 3)
+//!
+//! An instruction looks like `name(1,22,333)`: a name, then `arity`
+//! comma separated unsigned numbers of 1 to `max_digits` digits in
+//! parentheses, each fitting in a `u64`. Anything that does not match
+//! exactly is skipped.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Produces a value from its arguments
+    Call,
+    /// Turns following calls on
+    Enable,
+    /// Turns following calls off
+    Disable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub name: String,
+    pub arity: usize,
+    pub max_digits: usize,
+    pub kind: Kind,
+}
+
+impl Instruction {
+    pub fn call(name: &str, arity: usize, max_digits: usize) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            arity,
+            max_digits,
+            kind: Kind::Call,
+        }
+    }
+    pub fn enable(name: &str) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            arity: 0,
+            max_digits: 0,
+            kind: Kind::Enable,
+        }
+    }
+    pub fn disable(name: &str) -> Instruction {
+        Instruction {
+            name: name.to_string(),
+            arity: 0,
+            max_digits: 0,
+            kind: Kind::Disable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Byte offset of the start of the instruction name
+    pub offset: usize,
+    /// Index into the scanner's instruction set
+    pub instruction: usize,
+    pub args: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    instructions: Vec<Instruction>,
+}
+
+impl Scanner {
+    pub fn new(instructions: Vec<Instruction>) -> Scanner {
+        Scanner { instructions }
+    }
+    /// `mul(a,b)` with `do()` and `don't()` as in day 3
+    pub fn day3() -> Scanner {
+        Scanner::new(vec![
+            Instruction::call("mul", 2, 3),
+            Instruction::enable("do"),
+            Instruction::disable("don't"),
+        ])
+    }
+    pub fn instruction(&self, token: &Token) -> &Instruction {
+        &self.instructions[token.instruction]
+    }
+    /// Lazily yield every instruction in `input` in order
+    pub fn scan<'a>(&'a self, input: &'a str) -> Tokens<'a> {
+        Tokens {
+            scanner: self,
+            input: input.as_bytes(),
+            position: 0,
+        }
+    }
+    /// Like `scan`, but only yield calls that are not switched off by a
+    /// preceding disable instruction
+    pub fn scan_enabled<'a>(&'a self, input: &'a str) -> impl Iterator<Item = Token> + 'a {
+        let mut enabled = true;
+        self.scan(input)
+            .filter(move |token| match self.instruction(token).kind {
+                Kind::Call => enabled,
+                Kind::Enable => {
+                    enabled = true;
+                    false
+                }
+                Kind::Disable => {
+                    enabled = false;
+                    false
+                }
+            })
+    }
+    /// Try to read instruction `index` at the start of `input`
+    /// Returns the arguments and the number of bytes consumed
+    fn match_at(&self, index: usize, input: &[u8]) -> Option<(Vec<u64>, usize)> {
+        let instruction = &self.instructions[index];
+        let mut position = instruction.name.len();
+        if !input.starts_with(instruction.name.as_bytes()) || input.get(position) != Some(&b'(') {
+            return None;
+        }
+        position += 1;
+        let mut args = Vec::with_capacity(instruction.arity);
+        for i in 0..instruction.arity {
+            if i > 0 {
+                if input.get(position) != Some(&b',') {
+                    return None;
+                }
+                position += 1;
+            }
+            let digits = input[position..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            if digits == 0 || digits > instruction.max_digits {
+                return None;
+            }
+            let arg = input[position..position + digits]
+                .iter()
+                .try_fold(0u64, |acc, c| {
+                    acc.checked_mul(10)?.checked_add((c - b'0') as u64)
+                })?;
+            args.push(arg);
+            position += digits;
+        }
+        if input.get(position) != Some(&b')') {
+            return None;
+        }
+        Some((args, position + 1))
+    }
+}
+
+pub struct Tokens<'a> {
+    scanner: &'a Scanner,
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.input.len() {
+            let rest = &self.input[self.position..];
+            let found = (0..self.scanner.instructions.len())
+                .find_map(|index| Some((index, self.scanner.match_at(index, rest)?)));
+            let offset = self.position;
+            if let Some((instruction, (args, length))) = found {
+                self.position += length;
+                return Some(Token {
+                    offset,
+                    instruction,
+                    args,
+                });
+            }
+            self.position += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzz;
+
+    #[test]
+    fn test_scan() {
+        let scanner = Scanner::day3();
+        let tokens = scanner
+            .scan("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))")
+            .collect::<Vec<_>>();
+        let offsets = tokens.iter().map(|t| t.offset).collect::<Vec<_>>();
+        assert_eq!(offsets, [1, 20, 28, 48, 59, 64]);
+        assert_eq!(tokens[0].args, [2, 4]);
+        assert_eq!(scanner.instruction(&tokens[1]).kind, Kind::Disable);
+        assert_eq!(scanner.instruction(&tokens[4]).kind, Kind::Enable);
+    }
+    #[test]
+    fn test_scan_enabled() {
+        let scanner = Scanner::day3();
+        let out = scanner
+            .scan_enabled(include_str!("bin/03_testb.txt"))
+            .map(|t| t.args.iter().product::<u64>())
+            .sum::<u64>();
+        assert_eq!(out, 48);
+    }
+    #[test]
+    fn test_digit_limit() {
+        let scanner = Scanner::new(vec![Instruction::call("add", 3, 2)]);
+        let tokens = scanner
+            .scan("add(1,2,3)add(1,234,5)add(1,2)add(10,20,30)")
+            .map(|t| t.args)
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, [vec![1, 2, 3], vec![10, 20, 30]]);
+    }
+    #[test]
+    fn test_overflow() {
+        let scanner = Scanner::new(vec![Instruction::call("f", 1, 25)]);
+        assert_eq!(scanner.scan("f(99999999999999999999999)").count(), 0);
+        let tokens = scanner
+            .scan("f(18446744073709551615)f(18446744073709551616)")
+            .map(|t| t.args)
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, [vec![u64::MAX]]);
+    }
+    #[test]
+    fn test_fuzz() {
+        let scanner = Scanner::day3();
+        let seeds = [
+            include_str!("bin/03_test.txt"),
+            include_str!("bin/03_testb.txt"),
+        ];
+        fuzz::fuzz(0, &seeds, 300, |s| {
+            scanner
+                .scan_enabled(s)
+                .map(|t| t.args.iter().product::<u64>())
+                .sum::<u64>();
+        });
+    }
+}
@@ -0,0 +1,23 @@
+pub mod answer;
+pub mod antennas;
+pub mod bit_matrix;
+pub mod claw_machine;
+pub mod disk;
+pub mod equation;
+pub mod fuzz;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod instructions;
+pub mod location_lists;
+pub mod logging;
+pub mod market;
+pub mod page_order;
+pub mod parallel;
+pub mod regions;
+pub mod reports;
+pub mod schematics;
+pub mod stones;
+pub mod towels;
+pub mod trails;
+pub mod word_search;
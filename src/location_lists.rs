@@ -0,0 +1,248 @@
+//! Comparing columns of location ids, as in the day 1 historian lists
+//!
+//! Input is whitespace separated numbers with the same count on every
+//! line, read one number at a time straight into the columns.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::BufRead,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lists {
+    columns: Vec<Vec<usize>>,
+}
+
+impl Lists {
+    fn new() -> Lists {
+        Lists {
+            columns: Vec::new(),
+        }
+    }
+    /// Add the numbers from one line, the first line sets the column count
+    /// Blank lines are skipped
+    fn push_line(&mut self, line: &str) -> Option<()> {
+        if line.trim().is_empty() {
+            return Some(());
+        }
+        let first = self.columns.is_empty();
+        let mut count = 0;
+        for number in line.split_ascii_whitespace() {
+            if first {
+                self.columns.push(Vec::new());
+            }
+            self.columns.get_mut(count)?.push(number.parse().ok()?);
+            count += 1;
+        }
+        (count == self.columns.len()).then_some(())
+    }
+    /// Read lines as they come, reusing one buffer, None on a bad line
+    pub fn read(mut reader: impl BufRead) -> Option<Lists> {
+        let mut lists = Lists::new();
+        let mut line = String::new();
+        while reader.read_line(&mut line).ok()? > 0 {
+            lists.push_line(&line)?;
+            line.clear();
+        }
+        Some(lists)
+    }
+    pub fn columns(&self) -> usize {
+        self.columns.len()
+    }
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, |c| c.len())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn column(&self, index: usize) -> &[usize] {
+        &self.columns[index]
+    }
+}
+
+impl FromStr for Lists {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lists = Lists::new();
+        for line in s.lines() {
+            lists.push_line(line).ok_or(())?;
+        }
+        Ok(lists)
+    }
+}
+
+/// Sum of differences after sorting both lists, pairing smallest first
+/// None if the sum overflows
+pub fn distance(a: &[usize], b: &[usize]) -> Option<usize> {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_unstable();
+    b.sort_unstable();
+    a.iter()
+        .zip(&b)
+        .try_fold(0usize, |sum, (x, y)| sum.checked_add(x.abs_diff(*y)))
+}
+
+fn counts(values: &[usize]) -> HashMap<usize, usize> {
+    let mut out = HashMap::new();
+    for &value in values {
+        *out.entry(value).or_default() += 1;
+    }
+    out
+}
+
+/// Each value in `a` times how often it shows up in `b`, None if the
+/// sum overflows
+pub fn similarity(a: &[usize], b: &[usize]) -> Option<usize> {
+    let counts = counts(b);
+    a.iter().try_fold(0usize, |sum, x| {
+        sum.checked_add(x.checked_mul(*counts.get(x).unwrap_or(&0))?)
+    })
+}
+
+/// Values in one list but not the other, with how many extra copies
+pub fn symmetric_difference(a: &[usize], b: &[usize]) -> BTreeMap<usize, usize> {
+    let a = counts(a);
+    let b = counts(b);
+    let mut out = BTreeMap::new();
+    for (&value, &count) in &a {
+        let other = b.get(&value).copied().unwrap_or(0);
+        if count != other {
+            out.insert(value, count.abs_diff(other));
+        }
+    }
+    for (&value, &count) in &b {
+        if !a.contains_key(&value) {
+            out.insert(value, count);
+        }
+    }
+    out
+}
+
+/// Pairs within each run of equal values
+fn tied_pairs<T: PartialEq + Copy>(values: impl Iterator<Item = T>) -> usize {
+    let mut out = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for value in values {
+        if Some(value) == previous {
+            run += 1;
+        } else {
+            out += run * (run + 1) / 2;
+            run = 0;
+            previous = Some(value);
+        }
+    }
+    out + run * (run + 1) / 2
+}
+
+/// Merge sort that returns the number of swaps it had to make
+fn count_swaps(values: &mut [usize]) -> usize {
+    if values.len() < 2 {
+        return 0;
+    }
+    let middle = values.len() / 2;
+    let mut swaps = count_swaps(&mut values[..middle]) + count_swaps(&mut values[middle..]);
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, middle);
+    while i < middle && j < values.len() {
+        if values[i] <= values[j] {
+            merged.push(values[i]);
+            i += 1;
+        } else {
+            merged.push(values[j]);
+            swaps += middle - i;
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..middle]);
+    merged.extend_from_slice(&values[j..]);
+    values.copy_from_slice(&merged);
+    swaps
+}
+
+/// Kendall tau-b rank correlation of the rows `(a[i], b[i])`, using
+/// Knight's O(n log n) method. None if either list is all one value
+pub fn kendall_tau(a: &[usize], b: &[usize]) -> Option<f64> {
+    let mut pairs = a.iter().copied().zip(b.iter().copied()).collect::<Vec<_>>();
+    pairs.sort_unstable();
+    let n = pairs.len();
+    let all = n * n.saturating_sub(1) / 2;
+    let tied_a = tied_pairs(pairs.iter().map(|p| p.0));
+    let tied_both = tied_pairs(pairs.iter());
+    let mut b_sorted = pairs.iter().map(|p| p.1).collect::<Vec<_>>();
+    let swaps = count_swaps(&mut b_sorted);
+    let tied_b = tied_pairs(b_sorted.into_iter());
+    let denominator = ((all - tied_a) as f64 * (all - tied_b) as f64).sqrt();
+    if denominator == 0.0 {
+        return None;
+    }
+    let numerator = (all + tied_both) as f64 - (tied_a + tied_b + 2 * swaps) as f64;
+    Some(numerator / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let lists = include_str!("bin/01_test.txt").parse::<Lists>().unwrap();
+        assert_eq!(lists.columns(), 2);
+        assert_eq!(lists.len(), 6);
+        let read = Lists::read(include_str!("bin/01_test.txt").as_bytes()).unwrap();
+        assert_eq!(read, lists);
+        let three = "1 2 3\n4 5 6".parse::<Lists>().unwrap();
+        assert_eq!(three.column(2), [3, 6]);
+        assert!("1 2\n3".parse::<Lists>().is_err());
+        assert!("1 2\n3 4 5".parse::<Lists>().is_err());
+        assert!("1 x".parse::<Lists>().is_err());
+    }
+    #[test]
+    fn test_example() {
+        let lists = include_str!("bin/01_test.txt").parse::<Lists>().unwrap();
+        assert_eq!(distance(lists.column(0), lists.column(1)), Some(11));
+        assert_eq!(similarity(lists.column(0), lists.column(1)), Some(31));
+        let max = usize::MAX;
+        assert_eq!(similarity(&[max, max], &[max, max]), None);
+        assert_eq!(similarity(&[max], &[max]), Some(max));
+        assert_eq!(distance(&[0, 0], &[max, max]), None);
+        assert_eq!(distance(&[0], &[max]), Some(max));
+        assert_eq!(
+            symmetric_difference(lists.column(0), lists.column(1)),
+            BTreeMap::from([(1, 1), (2, 1), (5, 1), (9, 1)])
+        );
+    }
+    #[test]
+    fn test_kendall_tau() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(kendall_tau(&a, &[2, 4, 6, 8, 10]), Some(1.0));
+        assert_eq!(kendall_tau(&a, &[5, 4, 3, 2, 1]), Some(-1.0));
+        assert_eq!(kendall_tau(&a, &[1, 1, 1, 1, 1]), None);
+        // compare against counting every pair by hand, with ties
+        let a = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let b = [2, 7, 1, 8, 2, 8, 1, 8, 2, 8];
+        let (mut concordant, mut discordant, mut ties_a, mut ties_b) = (0, 0, 0, 0);
+        for i in 0..a.len() {
+            for j in i + 1..a.len() {
+                let sign =
+                    (a[i] as i64 - a[j] as i64).signum() * (b[i] as i64 - b[j] as i64).signum();
+                match sign {
+                    1 => concordant += 1,
+                    -1 => discordant += 1,
+                    _ => (),
+                }
+                ties_a += usize::from(a[i] == a[j]);
+                ties_b += usize::from(b[i] == b[j]);
+            }
+        }
+        let all = a.len() * (a.len() - 1) / 2;
+        let expected =
+            (concordant - discordant) as f64 / (((all - ties_a) * (all - ties_b)) as f64).sqrt();
+        let tau = kendall_tau(&a, &b).unwrap();
+        assert!((tau - expected).abs() < 1e-12);
+    }
+}
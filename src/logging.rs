@@ -0,0 +1,144 @@
+//! Logger behind the `log` macros, configured at runtime from `AOC_LOG`
+//!
+//! `AOC_LOG=debug` sets the level for everything, `AOC_LOG=warn,16b=trace`
+//! adds a level for one target and everything under it. Days log under
+//! their own name like `16b`, library modules under `aoc24::market` and so
+//! on. Output goes to stderr so the answers on stdout stay clean, and
+//! nothing is logged until `init` is called, so tests stay quiet.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::str::FromStr;
+
+pub const VARIABLE: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+/// Comma separated `level` or `target=level` directives, later ones win
+impl FromStr for Filter {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Filter {
+            default: LevelFilter::Off,
+            targets: Vec::new(),
+        };
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let level = level.trim().parse().map_err(|_| ())?;
+                    let target = target.trim();
+                    filter.targets.retain(|(t, _)| t != target);
+                    filter.targets.push((target.to_string(), level));
+                }
+                None => filter.default = directive.parse().map_err(|_| ())?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+impl Filter {
+    /// Level for the most specific directive covering the target
+    pub fn level(&self, target: &str) -> LevelFilter {
+        let target = day_name(target);
+        self.targets
+            .iter()
+            .filter(|(t, _)| {
+                target
+                    .strip_prefix(t.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(t, _)| t.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+    /// Most verbose level any target can use
+    pub fn max(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+/// Binaries are crates like `_16b`, so drop the underscore rustc adds
+fn day_name(target: &str) -> &str {
+    match target.strip_prefix('_') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest,
+        _ => target,
+    }
+}
+
+struct Logger {
+    filter: Filter,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level(metadata.target())
+    }
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let target = day_name(record.target());
+            eprintln!("[{} {}] {}", record.level(), target, record.args());
+        }
+    }
+    fn flush(&self) {}
+}
+
+/// Start logging as `AOC_LOG` says, warnings only if it's unset or bad
+pub fn init() {
+    let filter = std::env::var(VARIABLE)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(Filter {
+            default: LevelFilter::Warn,
+            targets: Vec::new(),
+        });
+    let max = filter.max();
+    // a logger can only be set once, later calls keep the first
+    if log::set_boxed_logger(Box::new(Logger { filter })).is_ok() {
+        log::set_max_level(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let filter = "warn,16b=trace,aoc24::market=debug"
+            .parse::<Filter>()
+            .unwrap();
+        assert_eq!(filter.level("_16b"), LevelFilter::Trace);
+        assert_eq!(filter.level("16b"), LevelFilter::Trace);
+        assert_eq!(filter.level("_16a"), LevelFilter::Warn);
+        assert_eq!(filter.level("aoc24::market"), LevelFilter::Debug);
+        assert_eq!(filter.level("aoc24::marketing"), LevelFilter::Warn);
+        assert_eq!(filter.level("aoc24::stones"), LevelFilter::Warn);
+        assert_eq!(filter.max(), LevelFilter::Trace);
+    }
+    #[test]
+    fn test_most_specific() {
+        let filter = "aoc24=info,aoc24::disk=off,debug"
+            .parse::<Filter>()
+            .unwrap();
+        assert_eq!(filter.level("aoc24::disk"), LevelFilter::Off);
+        assert_eq!(filter.level("aoc24::graph"), LevelFilter::Info);
+        assert_eq!(filter.level("_20b"), LevelFilter::Debug);
+        let filter = "20b=debug,20b=error".parse::<Filter>().unwrap();
+        assert_eq!(filter.level("20b"), LevelFilter::Error);
+        assert_eq!(filter.max(), LevelFilter::Error);
+    }
+    #[test]
+    fn test_bad_filter() {
+        assert!("loud".parse::<Filter>().is_err());
+        assert!("16b=loud".parse::<Filter>().is_err());
+        assert_eq!("".parse::<Filter>().unwrap().max(), LevelFilter::Off);
+        assert_eq!(day_name("_private"), "_private");
+    }
+}
@@ -0,0 +1,262 @@
+//! Monkey market for day 22
+//!
+//! A run of four price changes, each in -9..=9, is packed into one index
+//! below 19^4, so the bananas for every run of changes across all buyers
+//! add up in a single flat array.
+//!
+//! Evolving a secret only shifts, XORs and keeps the low 24 bits, so it is
+//! a linear map on GF(2)^24 and can be jumped forwards or backwards any
+//! number of steps with a bit matrix.
+
+use crate::{bit_matrix::BitMatrix, parallel};
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Secrets only ever keep this many bits
+pub const BITS: usize = 24;
+
+/// Number of possible runs of four price changes
+pub const SEQUENCES: usize = 19 * 19 * 19 * 19;
+
+/// Buyers totalled together on one thread before the totals are merged
+const CHUNK: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secret {
+    pub value: usize,
+}
+
+impl Secret {
+    pub fn mix(&mut self, num: usize) {
+        self.value ^= num;
+    }
+    pub fn prune(&mut self) {
+        self.value %= 16777216;
+    }
+    pub fn evolve(&mut self) {
+        self.mix(self.value * 64);
+        self.prune();
+        self.mix(self.value / 32);
+        self.prune();
+        self.mix(self.value * 2048);
+        self.prune();
+    }
+    pub fn price(&self) -> i8 {
+        (self.value % 10) as i8
+    }
+    /// One evolve step as a matrix
+    pub fn step_matrix() -> BitMatrix {
+        BitMatrix::from_fn(BITS, |bit| {
+            let mut secret = Secret {
+                value: bit as usize,
+            };
+            secret.evolve();
+            secret.value as u64
+        })
+    }
+    /// Evolve `steps` times, or go back if `steps` is negative
+    /// Anything above the low 24 bits is lost, as with `evolve`
+    pub fn jump(&mut self, steps: i64) {
+        if steps == 0 {
+            return;
+        }
+        let step = Secret::step_matrix();
+        let matrix = if steps > 0 {
+            step.pow(steps as u64)
+        } else {
+            step.inverse()
+                .expect("evolve is invertible")
+                .pow(steps.unsigned_abs())
+        };
+        self.value = matrix.apply(self.value as u64 % (1 << BITS)) as usize;
+    }
+    /// Undo one `evolve`
+    pub fn unevolve(&mut self) {
+        self.jump(-1);
+    }
+    /// Steps until the secret comes back round to its current value
+    /// Baby-step giant-step, since the cycle can be up to 2^24 long
+    pub fn cycle_length(&self) -> usize {
+        let size = 1 << (BITS / 2);
+        let step = Secret::step_matrix();
+        let start = self.value as u64 % (1 << BITS);
+        // smallest j in 1..=size with step^j(start) == value
+        let mut baby = HashMap::with_capacity(size);
+        let mut value = start;
+        for j in 1..=size {
+            value = step.apply(value);
+            if let Entry::Vacant(entry) = baby.entry(value) {
+                entry.insert(j);
+            }
+        }
+        // step^(i * size + j)(start) == start when step^j(start) is the
+        // start taken back i * size steps
+        let back = step
+            .inverse()
+            .expect("evolve is invertible")
+            .pow(size as u64);
+        let mut value = start;
+        for i in 0.. {
+            if let Some(j) = baby.get(&value) {
+                return i * size + j;
+            }
+            value = back.apply(value);
+        }
+        unreachable!()
+    }
+}
+
+/// Index of a run of price changes, in the same order as the runs sort
+pub fn encode(sequence: [i8; 4]) -> usize {
+    sequence
+        .iter()
+        .fold(0, |index, &delta| index * 19 + (delta + 9) as usize)
+}
+
+pub fn decode(mut index: usize) -> [i8; 4] {
+    let mut sequence = [0; 4];
+    for delta in sequence.iter_mut().rev() {
+        *delta = (index % 19) as i8 - 9;
+        index /= 19;
+    }
+    sequence
+}
+
+/// Bananas earned by each run of price changes summed over all buyers
+#[derive(Debug)]
+pub struct SequenceTotals {
+    totals: Vec<u32>,
+}
+
+impl SequenceTotals {
+    pub fn new(secrets: &[Secret], steps: usize) -> SequenceTotals {
+        let chunks = secrets.chunks(CHUNK).collect::<Vec<_>>();
+        let partials = parallel::map(&chunks, |chunk| SequenceTotals::chunk(chunk, steps));
+        let mut totals = vec![0; SEQUENCES];
+        for partial in partials {
+            totals.iter_mut().zip(partial).for_each(|(t, p)| *t += p);
+        }
+        SequenceTotals { totals }
+    }
+    /// Totals for one group of buyers
+    fn chunk(secrets: &[Secret], steps: usize) -> Vec<u32> {
+        let mut totals = vec![0; SEQUENCES];
+        // buyer number + 1 that last saw each run, so nothing needs clearing
+        let mut seen = vec![0_u32; SEQUENCES];
+        for (buyer, &secret) in secrets.iter().enumerate() {
+            let stamp = buyer as u32 + 1;
+            let mut secret = secret;
+            let mut prev = secret.price();
+            let mut index = 0;
+            for step in 0..steps {
+                secret.evolve();
+                let price = secret.price();
+                index = (index * 19 + (price - prev + 9) as usize) % SEQUENCES;
+                prev = price;
+                // a buyer sells the first time a run turns up
+                if step >= 3 && seen[index] != stamp {
+                    seen[index] = stamp;
+                    totals[index] += price as u32;
+                }
+            }
+        }
+        totals
+    }
+    pub fn total(&self, sequence: [i8; 4]) -> usize {
+        self.totals[encode(sequence)] as usize
+    }
+    /// Run of changes that earns the most, ties go to the first in order
+    pub fn best(&self) -> ([i8; 4], usize) {
+        let (index, total) = self
+            .totals
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, total)| total)
+            .unwrap();
+        (decode(index), *total as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mix() {
+        let mut secret = Secret { value: 42 };
+        secret.mix(15);
+        assert_eq!(secret.value, 37);
+    }
+    #[test]
+    fn test_prune() {
+        let mut secret = Secret { value: 100000000 };
+        secret.prune();
+        assert_eq!(secret.value, 16113920);
+    }
+    #[test]
+    fn test_evolve() {
+        let mut secret = Secret { value: 123 };
+        let expected = vec![
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254,
+        ];
+        for expected in expected {
+            secret.evolve();
+            assert_eq!(secret.value, expected);
+        }
+    }
+    #[test]
+    fn test_jump() {
+        let mut secret = Secret { value: 123 };
+        secret.jump(10);
+        assert_eq!(secret.value, 5908254);
+        secret.unevolve();
+        assert_eq!(secret.value, 7753432);
+        secret.jump(-9);
+        assert_eq!(secret.value, 123);
+        let mut slow = Secret { value: 123 };
+        (0..2000).for_each(|_| slow.evolve());
+        secret.jump(2000);
+        assert_eq!(secret, slow);
+        secret.jump(-1_000_000_000_000);
+        secret.jump(1_000_000_000_000);
+        assert_eq!(secret, slow);
+    }
+    #[test]
+    fn test_cycle_length() {
+        let secret = Secret { value: 123 };
+        let length = secret.cycle_length();
+        let mut jumped = secret;
+        jumped.jump(length as i64);
+        assert_eq!(jumped, secret);
+        assert_eq!(length, 16777215);
+        assert_eq!(Secret { value: 0 }.cycle_length(), 1);
+    }
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode([-9, -9, -9, -9]), 0);
+        assert_eq!(encode([9, 9, 9, 9]), SEQUENCES - 1);
+        assert_eq!(decode(encode([-2, 1, -1, 3])), [-2, 1, -1, 3]);
+        assert!(encode([-1, 0, 0, 0]) < encode([0, -9, 0, 0]));
+    }
+    #[test]
+    fn test_sequence() {
+        let totals = SequenceTotals::new(&[Secret { value: 123 }], 10);
+        assert_eq!(totals.total([-1, -1, 0, 2]), 6);
+        assert_eq!(totals.best(), ([-1, -1, 0, 2], 6));
+    }
+    #[test]
+    fn test_best() {
+        let secrets = [1, 2, 3, 2024].map(|value| Secret { value });
+        let totals = SequenceTotals::new(&secrets, 2000);
+        assert_eq!(totals.best(), ([-2, 1, -1, 3], 23));
+    }
+    #[test]
+    fn test_chunks() {
+        let secrets = (1..=2 * CHUNK + 10)
+            .map(|value| Secret { value })
+            .collect::<Vec<_>>();
+        let totals = SequenceTotals::new(&secrets, 200);
+        assert_eq!(totals.totals, SequenceTotals::chunk(&secrets, 200));
+    }
+}
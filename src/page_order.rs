@@ -0,0 +1,242 @@
+//! Page ordering rules for day 5
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+};
+
+/// `left` must be printed before `right`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (left, right) = s.split_once("|").ok_or(())?;
+        let left = left.parse::<usize>().map_err(|_| ())?;
+        let right = right.parse::<usize>().map_err(|_| ())?;
+        Ok(Rule { left, right })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    pub pages: Vec<usize>,
+}
+
+impl FromStr for Update {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pages = s
+            .split(",")
+            .map(|p| p.parse::<usize>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Update { pages })
+    }
+}
+
+/// A rule broken by an update
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: Rule,
+    /// Positions of the rule's left and right page, left comes after right
+    pub positions: [usize; 2],
+}
+
+/// Pages of an update whose rules contradict each other
+/// Each page must come before the next, and the last before the first
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub pages: Vec<usize>,
+}
+
+impl Update {
+    pub fn check(&self, ruleset: &Ruleset) -> Result<(), Vec<Violation>> {
+        let positions = self.positions();
+        let mut violations = Vec::new();
+        for (ileft, left) in self.pages.iter().enumerate() {
+            for right in ruleset.rights(*left) {
+                if let Some(&iright) = positions.get(&right) {
+                    if iright < ileft {
+                        violations.push(Violation {
+                            rule: Rule { left: *left, right },
+                            positions: [ileft, iright],
+                        });
+                    }
+                }
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+    pub fn middle(&self) -> usize {
+        self.pages[(self.pages.len() - 1) / 2]
+    }
+    /// Topological sort of the pages using only the rules between them
+    /// Pages that are free to go next keep their original order
+    pub fn reorder(&self, ruleset: &Ruleset) -> Result<Update, Cycle> {
+        let positions = self.positions();
+        let mut incoming = vec![0; self.pages.len()];
+        for page in &self.pages {
+            for right in ruleset.rights(*page) {
+                if let Some(&iright) = positions.get(&right) {
+                    incoming[iright] += 1;
+                }
+            }
+        }
+        let mut ready = (0..self.pages.len())
+            .filter(|&i| incoming[i] == 0)
+            .collect::<BTreeSet<_>>();
+        let mut pages = Vec::with_capacity(self.pages.len());
+        while let Some(i) = ready.pop_first() {
+            pages.push(self.pages[i]);
+            for right in ruleset.rights(self.pages[i]) {
+                if let Some(&iright) = positions.get(&right) {
+                    incoming[iright] -= 1;
+                    if incoming[iright] == 0 {
+                        ready.insert(iright);
+                    }
+                }
+            }
+        }
+        if pages.len() < self.pages.len() {
+            return Err(self.find_cycle(ruleset, &incoming));
+        }
+        Ok(Update { pages })
+    }
+    /// Every page left over by the sort still has a rule from another
+    /// left over page, so walking those rules backwards must repeat
+    fn find_cycle(&self, ruleset: &Ruleset, incoming: &[usize]) -> Cycle {
+        let remaining = (0..self.pages.len())
+            .filter(|&i| incoming[i] > 0)
+            .map(|i| self.pages[i])
+            .collect::<BTreeSet<_>>();
+        let mut walk = vec![*remaining.first().unwrap()];
+        loop {
+            let current = *walk.last().unwrap();
+            let previous = *remaining
+                .iter()
+                .find(|&&p| ruleset.rights(p).any(|r| r == current))
+                .unwrap();
+            if let Some(start) = walk.iter().position(|&p| p == previous) {
+                let mut pages = walk.split_off(start);
+                pages.reverse();
+                let smallest = (0..pages.len()).min_by_key(|&i| pages[i]).unwrap();
+                pages.rotate_left(smallest);
+                return Cycle { pages };
+            }
+            walk.push(previous);
+        }
+    }
+    fn positions(&self) -> HashMap<usize, usize> {
+        self.pages
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p, i))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct Ruleset {
+    rules: BTreeMap<usize, BTreeSet<usize>>,
+}
+
+impl Ruleset {
+    pub fn new(rules: &[Rule]) -> Self {
+        let mut ruleset: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        for Rule { left, right } in rules {
+            match ruleset.entry(*left) {
+                Entry::Vacant(e) => {
+                    e.insert(BTreeSet::from([*right]));
+                }
+                Entry::Occupied(mut e) => {
+                    e.get_mut().insert(*right);
+                }
+            }
+        }
+        Ruleset { rules: ruleset }
+    }
+    /// Pages that must come after `left`
+    fn rights(&self, left: usize) -> impl Iterator<Item = usize> + '_ {
+        self.rules.get(&left).into_iter().flatten().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_fail() {
+        let rules = vec![Rule { left: 1, right: 2 }];
+        let ruleset = Ruleset::new(&rules);
+        let update = Update { pages: vec![2, 1] };
+        assert_eq!(
+            update.check(&ruleset),
+            Err(vec![Violation {
+                rule: Rule { left: 1, right: 2 },
+                positions: [1, 0],
+            }])
+        );
+    }
+    #[test]
+    fn test_check_pass() {
+        let rules = vec![Rule { left: 1, right: 2 }];
+        let ruleset = Ruleset::new(&rules);
+        let update = Update { pages: vec![1, 2] };
+        assert!(update.check(&ruleset).is_ok());
+    }
+    #[test]
+    fn test_check_fail2() {
+        let rules = vec![Rule { left: 1, right: 2 }, Rule { left: 1, right: 3 }];
+        let ruleset = Ruleset::new(&rules);
+        let update = Update { pages: vec![3, 1] };
+        assert!(update.check(&ruleset).is_err());
+    }
+    #[test]
+    fn test_reorder() {
+        let rules = vec![
+            Rule { left: 1, right: 2 },
+            Rule { left: 2, right: 3 },
+            Rule { left: 4, right: 3 },
+        ];
+        let ruleset = Ruleset::new(&rules);
+        let update = Update {
+            pages: vec![3, 4, 2, 5, 1],
+        };
+        assert_eq!(update.reorder(&ruleset).unwrap().pages, [4, 5, 1, 2, 3]);
+    }
+    #[test]
+    fn test_cycle() {
+        let rules = vec![
+            Rule { left: 1, right: 2 },
+            Rule { left: 2, right: 3 },
+            Rule { left: 3, right: 1 },
+            Rule { left: 3, right: 4 },
+        ];
+        let ruleset = Ruleset::new(&rules);
+        let update = Update {
+            pages: vec![4, 3, 2, 1],
+        };
+        assert_eq!(
+            update.reorder(&ruleset),
+            Err(Cycle {
+                pages: vec![1, 2, 3]
+            })
+        );
+        // the cycle only matters if all its pages are in the update
+        let update = Update {
+            pages: vec![4, 3, 2],
+        };
+        assert_eq!(update.reorder(&ruleset).unwrap().pages, [2, 3, 4]);
+    }
+}
@@ -0,0 +1,84 @@
+//! Map over independent work items, on a thread pool when the `parallel`
+//! feature is enabled and serially otherwise
+//!
+//! Results always come back in the same order as the inputs
+
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.iter().map(f).collect()
+}
+
+#[cfg(feature = "parallel")]
+pub fn map_mut<T, R, F>(items: &mut [T], f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut T) -> R + Sync + Send,
+{
+    items.par_iter_mut().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map_mut<T, R, F>(items: &mut [T], f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut T) -> R + Sync + Send,
+{
+    items.iter_mut().map(f).collect()
+}
+
+#[cfg(feature = "parallel")]
+pub fn map_range<R, F>(range: Range<usize>, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync + Send,
+{
+    range.into_par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map_range<R, F>(range: Range<usize>, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync + Send,
+{
+    range.map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order() {
+        let items = (0..1000).collect::<Vec<usize>>();
+        assert_eq!(map(&items, |x| x * 2), map_range(0..1000, |x| x * 2));
+        let mut items = items;
+        let out = map_mut(&mut items, |x| {
+            *x += 1;
+            *x
+        });
+        assert_eq!(out, (1..1001).collect::<Vec<_>>());
+    }
+}
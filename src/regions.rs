@@ -0,0 +1,251 @@
+//! Connected regions of equal values in a grid, as in the day 12 garden
+
+use ndarray::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Edge neighbours only
+    Four,
+    /// Edge and corner neighbours
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(&self) -> &'static [[i64; 2]] {
+        match self {
+            Connectivity::Four => &[[-1, 0], [1, 0], [0, -1], [0, 1]],
+            Connectivity::Eight => &[
+                [-1, 0],
+                [1, 0],
+                [0, -1],
+                [0, 1],
+                [-1, -1],
+                [-1, 1],
+                [1, -1],
+                [1, 1],
+            ],
+        }
+    }
+    /// Connectivity to use for the background so holes are well defined
+    fn dual(&self) -> Connectivity {
+        match self {
+            Connectivity::Four => Connectivity::Eight,
+            Connectivity::Eight => Connectivity::Four,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<T> {
+    pub value: T,
+    pub area: usize,
+    /// Number of cell edges between the region and anything else
+    pub perimeter: usize,
+    /// Number of straight fence sections
+    pub sides: usize,
+    /// Smallest and largest `[row, col]` in the region
+    pub bounding_box: [[usize; 2]; 2],
+    /// First cell of the region in row major order
+    pub start: [usize; 2],
+    pub connectivity: Connectivity,
+}
+
+fn offset(position: [usize; 2], offset: [i64; 2], shape: &[usize]) -> Option<[usize; 2]> {
+    let row = usize::try_from(position[0] as i64 + offset[0]).ok()?;
+    let col = usize::try_from(position[1] as i64 + offset[1]).ok()?;
+    (row < shape[0] && col < shape[1]).then_some([row, col])
+}
+
+/// Flood fill `inside` cells into components
+/// Labels count up from 0 in the order regions are first met
+fn label_mask(
+    inside: ArrayView2<bool>,
+    connectivity: Connectivity,
+) -> (Array2<Option<usize>>, usize) {
+    let shape = inside.shape();
+    let mut labels = Array2::from_elem(inside.raw_dim(), None);
+    let mut count = 0;
+    let mut stack = Vec::new();
+    for ((row, col), &is_inside) in inside.indexed_iter() {
+        if !is_inside || labels[[row, col]].is_some() {
+            continue;
+        }
+        labels[[row, col]] = Some(count);
+        stack.push([row, col]);
+        while let Some(position) = stack.pop() {
+            for &o in connectivity.offsets() {
+                if let Some(next) = offset(position, o, shape) {
+                    if inside[next] && labels[next].is_none() {
+                        labels[next] = Some(count);
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        count += 1;
+    }
+    (labels, count)
+}
+
+/// Label each cell with its region, returning the labels and region count
+pub fn label<T: PartialEq>(
+    map: ArrayView2<T>,
+    connectivity: Connectivity,
+) -> (Array2<usize>, usize) {
+    let shape = map.shape();
+    let mut labels = Array2::from_elem(map.raw_dim(), usize::MAX);
+    let mut count = 0;
+    let mut stack = Vec::new();
+    for ((row, col), value) in map.indexed_iter() {
+        if labels[[row, col]] != usize::MAX {
+            continue;
+        }
+        labels[[row, col]] = count;
+        stack.push([row, col]);
+        while let Some(position) = stack.pop() {
+            for &o in connectivity.offsets() {
+                if let Some(next) = offset(position, o, shape) {
+                    if labels[next] == usize::MAX && map[next] == *value {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        count += 1;
+    }
+    (labels, count)
+}
+
+/// Count corners of the region, each corner starts a new side
+/// Looking at one corner of a cell with its two edge neighbours and the
+/// diagonal: outside corner if both edge neighbours are outside, inside
+/// corner if both are in but the diagonal is not
+fn corners(labels: ArrayView2<usize>, position: [usize; 2]) -> usize {
+    let shape = labels.shape();
+    let label = labels[position];
+    let inside = |o: [i64; 2]| offset(position, o, shape).is_some_and(|p| labels[p] == label);
+    [[-1, -1], [-1, 1], [1, -1], [1, 1]]
+        .into_iter()
+        .filter(|&[dr, dc]| {
+            let vertical = inside([dr, 0]);
+            let horizontal = inside([0, dc]);
+            (!vertical && !horizontal) || (vertical && horizontal && !inside([dr, dc]))
+        })
+        .count()
+}
+
+impl<T: PartialEq> Region<T> {
+    /// Areas fully enclosed by the region, given the map it came from
+    /// Found as the components of everything else in the bounding box,
+    /// padded by one so all that reaches the outside joins up
+    pub fn holes(&self, map: ArrayView2<T>) -> usize {
+        let [[min_row, min_col], [max_row, max_col]] = self.bounding_box;
+        let shape = [max_row - min_row + 3, max_col - min_col + 3];
+        let mut same = Array2::from_elem(shape, false);
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                same[[row - min_row + 1, col - min_col + 1]] = map[[row, col]] == self.value;
+            }
+        }
+        // other regions of the same value in the box count as background
+        let (components, _) = label_mask(same.view(), self.connectivity);
+        let label = components[[self.start[0] - min_row + 1, self.start[1] - min_col + 1]];
+        let outside = components.map(|&c| c != label);
+        let (background, count) = label_mask(outside.view(), self.connectivity.dual());
+        // the padding is all one component and always the first met
+        debug_assert_eq!(background[[0, 0]], Some(0));
+        count - 1
+    }
+}
+
+/// Every region of equal values with its measurements, in label order
+pub fn regions<T: PartialEq + Clone>(
+    map: ArrayView2<T>,
+    connectivity: Connectivity,
+) -> Vec<Region<T>> {
+    let (labels, count) = label(map, connectivity);
+    let mut regions: Vec<Option<Region<T>>> = vec![None; count];
+    let shape = map.shape();
+    for ((row, col), &label) in labels.indexed_iter() {
+        let position = [row, col];
+        let region = regions[label].get_or_insert_with(|| Region {
+            value: map[position].clone(),
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            bounding_box: [position, position],
+            start: position,
+            connectivity,
+        });
+        region.area += 1;
+        region.perimeter += Connectivity::Four
+            .offsets()
+            .iter()
+            .filter(|&&o| offset(position, o, shape).is_none_or(|p| labels[p] != label))
+            .count();
+        region.sides += corners(labels.view(), position);
+        let [min, max] = &mut region.bounding_box;
+        *min = [min[0].min(row), min[1].min(col)];
+        *max = [max[0].max(row), max[1].max(col)];
+    }
+    regions.into_iter().map(Option::unwrap).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    fn parse(s: &str) -> Array2<char> {
+        grid::parse(s, |_, c| Some(c)).unwrap()
+    }
+    #[test]
+    fn test_example() {
+        let map = parse("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = regions(map.view(), Connectivity::Four);
+        let summary = regions
+            .iter()
+            .map(|r| (r.value, r.area, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        assert_eq!(regions[2].bounding_box, [[1, 2], [3, 3]]);
+    }
+    #[test]
+    fn test_holes() {
+        let map = parse(include_str!("bin/12_test_b.txt"));
+        let regions = regions(map.view(), Connectivity::Four);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0].holes(map.view()), 4);
+        assert_eq!(regions[0].sides, 20);
+        assert!(regions[1..].iter().all(|r| r.holes(map.view()) == 0));
+        // the middle A is a region of its own, so a hole in the outer A
+        let map = parse("AAAAA\nABBBA\nABABA\nABBBA\nAAAAA");
+        let holes = super::regions(map.view(), Connectivity::Four)
+            .iter()
+            .map(|r| (r.value, r.start, r.holes(map.view())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            holes,
+            [('A', [0, 0], 1), ('B', [1, 1], 1), ('A', [2, 2], 0)]
+        );
+    }
+    #[test]
+    fn test_connectivity() {
+        let map = parse("AB\nBA");
+        assert_eq!(label(map.view(), Connectivity::Four).1, 4);
+        let regions = regions(map.view(), Connectivity::Eight);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].area, 2);
+        assert_eq!(regions[0].sides, 8);
+    }
+}
@@ -0,0 +1,117 @@
+//! Reactor report safety for day 2
+//!
+//! A report is safe when its levels all increase or all decrease with every
+//! step inside an allowed range, after removing at most a few levels.
+
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct Analyzer {
+    pub steps: RangeInclusive<usize>,
+    pub max_removals: usize,
+}
+
+impl Analyzer {
+    pub fn new(steps: RangeInclusive<usize>, max_removals: usize) -> Analyzer {
+        Analyzer {
+            steps,
+            max_removals,
+        }
+    }
+    /// Indices to remove to make the report safe, as few as possible
+    /// None if more than `max_removals` levels would have to go
+    pub fn analyze(&self, levels: &[usize]) -> Option<Vec<usize>> {
+        let increasing = self.analyze_direction(levels, true);
+        let decreasing = self.analyze_direction(levels, false);
+        match (increasing, decreasing) {
+            (Some(a), Some(b)) if b.len() < a.len() => Some(b),
+            (Some(a), _) => Some(a),
+            (None, b) => b,
+        }
+    }
+    pub fn safe(&self, levels: &[usize]) -> bool {
+        self.analyze(levels).is_some()
+    }
+    fn step_ok(&self, from: usize, to: usize, increasing: bool) -> bool {
+        let step = if increasing {
+            to.checked_sub(from)
+        } else {
+            from.checked_sub(to)
+        };
+        step.is_some_and(|step| self.steps.contains(&step))
+    }
+    /// `removals[i]` is the fewest removals with level `i` the last one kept
+    /// Only the previous `max_removals + 1` levels can come before it, so
+    /// this is linear in the report length
+    fn analyze_direction(&self, levels: &[usize], increasing: bool) -> Option<Vec<usize>> {
+        let n = levels.len();
+        if n == 0 {
+            return Some(Vec::new());
+        }
+        let k = self.max_removals;
+        let mut removals: Vec<Option<usize>> = vec![None; n];
+        let mut previous: Vec<Option<usize>> = vec![None; n];
+        for i in 0..n {
+            if i <= k {
+                removals[i] = Some(i);
+            }
+            for p in i.saturating_sub(k + 1)..i {
+                let Some(before) = removals[p] else {
+                    continue;
+                };
+                let total = before + (i - p - 1);
+                if total > k || !self.step_ok(levels[p], levels[i], increasing) {
+                    continue;
+                }
+                if removals[i].is_none_or(|r| total < r) {
+                    removals[i] = Some(total);
+                    previous[i] = Some(p);
+                }
+            }
+        }
+        let (last, _) = (n.saturating_sub(k + 1)..n)
+            .filter_map(|i| Some((i, removals[i]? + (n - 1 - i))))
+            .filter(|&(_, total)| total <= k)
+            .min_by_key(|&(_, total)| total)?;
+        let mut kept = vec![false; n];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = previous[i];
+        }
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_removals() {
+        let analyzer = Analyzer::new(1..=3, 0);
+        assert_eq!(analyzer.analyze(&[7, 6, 4, 2, 1]), Some(vec![]));
+        assert_eq!(analyzer.analyze(&[1, 2, 7, 8, 9]), None);
+        assert_eq!(analyzer.analyze(&[1, 3, 2, 4, 5]), None);
+    }
+    #[test]
+    fn test_one_removal() {
+        let analyzer = Analyzer::new(1..=3, 1);
+        assert_eq!(analyzer.analyze(&[1, 2, 7, 8, 9]), None);
+        assert_eq!(analyzer.analyze(&[9, 7, 6, 2, 1]), None);
+        // removing either the 3 or the 2 works
+        assert_eq!(analyzer.analyze(&[1, 3, 2, 4, 5]), Some(vec![2]));
+        assert_eq!(analyzer.analyze(&[8, 6, 4, 4, 1]), Some(vec![3]));
+        // first and last levels can go too
+        assert_eq!(analyzer.analyze(&[9, 1, 2, 3]), Some(vec![0]));
+        assert_eq!(analyzer.analyze(&[1, 2, 3, 9]), Some(vec![3]));
+    }
+    #[test]
+    fn test_more_removals() {
+        let analyzer = Analyzer::new(2..=5, 2);
+        assert_eq!(analyzer.analyze(&[1, 3, 4, 5, 7]), Some(vec![2]));
+        assert_eq!(analyzer.analyze(&[1, 2, 2, 4]).unwrap().len(), 2);
+        assert!(!Analyzer::new(2..=5, 1).safe(&[1, 2, 2, 4]));
+        assert_eq!(analyzer.analyze(&[1, 1, 1, 1]), None);
+    }
+}
@@ -0,0 +1,277 @@
+//! Lock and key schematics for day 25
+//!
+//! Schematics can be any size as long as they all match. For each column
+//! and pin height there is a bitset of the keys short enough to fit, so the
+//! keys fitting a lock are one AND across its columns.
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Pins hang down from a filled top row
+    Lock,
+    /// Pins stand up from a filled bottom row
+    Key,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub kind: Kind,
+    /// Pin length in each column, not counting the filled row
+    pub heights: Vec<usize>,
+    /// Rows of room between the lock and key bases
+    pub space: usize,
+}
+
+impl FromStr for Schematic {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|c| match c {
+                        '#' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err(()),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = rows.first().ok_or(())?.len();
+        if rows.len() < 2 || width == 0 || rows.iter().any(|row| row.len() != width) {
+            return Err(());
+        }
+        let (kind, mut rows) = if rows[0].iter().all(|&c| c) {
+            (Kind::Lock, rows)
+        } else if rows[rows.len() - 1].iter().all(|&c| c) {
+            (Kind::Key, rows.into_iter().rev().collect())
+        } else {
+            return Err(());
+        };
+        rows.remove(0);
+        let space = rows.len() - 1;
+        let mut heights = Vec::with_capacity(width);
+        for column in 0..width {
+            let height = rows.iter().take_while(|row| row[column]).count();
+            // pins have no gaps and can't reach the other base
+            if height > space || rows[height..].iter().any(|row| row[column]) {
+                return Err(());
+            }
+            heights.push(height);
+        }
+        Ok(Schematic {
+            kind,
+            heights,
+            space,
+        })
+    }
+}
+
+impl Schematic {
+    /// Check one pair directly, with no pins overlapping
+    pub fn fits(&self, other: &Schematic) -> bool {
+        self.kind != other.kind
+            && self.heights.len() == other.heights.len()
+            && self
+                .heights
+                .iter()
+                .zip(&other.heights)
+                .all(|(a, b)| a + b <= self.space)
+    }
+}
+
+/// Set of key indices
+#[derive(Debug, Clone)]
+struct KeySet {
+    words: Vec<u64>,
+}
+
+impl KeySet {
+    fn new(size: usize) -> KeySet {
+        KeySet {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+    fn insert(&mut self, key: usize) {
+        self.words[key / 64] |= 1 << (key % 64);
+    }
+    fn intersect(&mut self, other: &KeySet) {
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, b)| *a &= b);
+    }
+    fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematics {
+    pub locks: Vec<Schematic>,
+    pub keys: Vec<Schematic>,
+    /// `short_keys[column][height]` holds keys no taller than `height` there
+    short_keys: Vec<Vec<KeySet>>,
+}
+
+impl FromStr for Schematics {
+    type Err = ();
+
+    /// Schematics separated by blank lines, all the same size
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let schematics = s
+            .trim_end()
+            .split("\n\n")
+            .map(|block| block.parse::<Schematic>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = &schematics[0];
+        if schematics
+            .iter()
+            .any(|s| s.space != first.space || s.heights.len() != first.heights.len())
+        {
+            return Err(());
+        }
+        let (locks, keys) = schematics.into_iter().partition(|s| s.kind == Kind::Lock);
+        Ok(Schematics::new(locks, keys))
+    }
+}
+
+impl Schematics {
+    /// All locks and keys must be the same size
+    pub fn new(locks: Vec<Schematic>, keys: Vec<Schematic>) -> Schematics {
+        let (width, space) = keys
+            .first()
+            .map_or((0, 0), |key| (key.heights.len(), key.space));
+        let mut short_keys = vec![vec![KeySet::new(keys.len()); space + 1]; width];
+        for (index, key) in keys.iter().enumerate() {
+            for (column, &height) in key.heights.iter().enumerate() {
+                for set in &mut short_keys[column][height..] {
+                    set.insert(index);
+                }
+            }
+        }
+        Schematics {
+            locks,
+            keys,
+            short_keys,
+        }
+    }
+    fn fitting_keys(&self, lock: &Schematic) -> KeySet {
+        let mut out = KeySet::new(self.keys.len());
+        out.words.fill(u64::MAX);
+        for (column, &height) in lock.heights.iter().enumerate() {
+            out.intersect(&self.short_keys[column][lock.space - height]);
+        }
+        out
+    }
+    /// Number of lock and key pairs that fit together
+    pub fn count_fits(&self) -> usize {
+        if self.keys.is_empty() {
+            return 0;
+        }
+        self.locks
+            .iter()
+            .map(|lock| self.fitting_keys(lock).len())
+            .sum()
+    }
+    /// Lock and key indices of every pair that fits together
+    pub fn fits(&self) -> Vec<(usize, usize)> {
+        if self.keys.is_empty() {
+            return Vec::new();
+        }
+        self.locks
+            .iter()
+            .enumerate()
+            .flat_map(|(ilock, lock)| {
+                self.fitting_keys(lock)
+                    .iter()
+                    .map(move |ikey| (ilock, ikey))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn test_parse() {
+        let lock = "###\n#.#\n...\n...".parse::<Schematic>().unwrap();
+        assert_eq!(lock.kind, Kind::Lock);
+        assert_eq!(lock.heights, [1, 0, 1]);
+        assert_eq!(lock.space, 2);
+        let key = "...\n.#.\n###".parse::<Schematic>().unwrap();
+        assert_eq!(key.kind, Kind::Key);
+        assert_eq!(key.heights, [0, 1, 0]);
+        // a pin with a gap
+        assert!("##\n.#\n#.\n..".parse::<Schematic>().is_err());
+        // not a lock or a key
+        assert!("#.\n..\n.#".parse::<Schematic>().is_err());
+    }
+    #[test]
+    fn test_fits() {
+        let schematics = include_str!("bin/25_test.txt")
+            .parse::<Schematics>()
+            .unwrap();
+        assert_eq!(schematics.locks.len(), 2);
+        assert_eq!(schematics.keys.len(), 3);
+        assert_eq!(schematics.count_fits(), 3);
+        let fits = schematics.fits();
+        assert_eq!(fits, [(0, 2), (1, 1), (1, 2)]);
+        for (ilock, lock) in schematics.locks.iter().enumerate() {
+            for (ikey, key) in schematics.keys.iter().enumerate() {
+                assert_eq!(lock.fits(key), fits.contains(&(ilock, ikey)));
+            }
+        }
+    }
+    #[test]
+    fn test_sizes() {
+        let mixed = "##\n..\n..\n\n..\n.#\n##".parse::<Schematics>();
+        assert!(mixed.is_ok());
+        let mixed = "##\n..\n..\n\n..\n##".parse::<Schematics>();
+        assert!(mixed.is_err());
+        // 70 keys so the bitsets take more than one word
+        let mut input = String::from("#\n#\n.\n.");
+        for i in 0..70 {
+            input += ["\n\n.\n.\n.\n#", "\n\n.\n.\n#\n#", "\n\n.\n#\n#\n#"][i % 3];
+        }
+        let schematics = input.parse::<Schematics>().unwrap();
+        assert_eq!(schematics.count_fits(), 47);
+    }
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(25);
+        for _ in 0..20 {
+            let input = generate::day25(&mut rng, 150, 4, 6);
+            let schematics = input.parse::<Schematics>().unwrap();
+            let mut expected = Vec::new();
+            for (ilock, lock) in schematics.locks.iter().enumerate() {
+                for (ikey, key) in schematics.keys.iter().enumerate() {
+                    if lock.fits(key) {
+                        expected.push((ilock, ikey));
+                    }
+                }
+            }
+            assert_eq!(schematics.fits(), expected);
+            assert_eq!(schematics.count_fits(), expected.len());
+        }
+    }
+}
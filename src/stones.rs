@@ -0,0 +1,262 @@
+//! Blinking stones for day 11
+//!
+//! Stone order never matters, so the stones are kept as a count of each
+//! value and every distinct value is rewritten once per blink.
+
+use std::collections::{HashMap, HashSet};
+
+pub fn digit_count(num: usize) -> usize {
+    let mut digits = 0;
+    let mut val = 1;
+    while val <= num {
+        val *= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// Left and right halves of the digits of `num`
+pub fn split_digits(num: usize, ndigits: usize) -> (usize, usize) {
+    let val = 10_usize.pow((ndigits / 2) as u32);
+    let left = num / val;
+    let right = num - left * val;
+    (left, right)
+}
+
+/// What one stone turns into after a blink
+pub trait Rule {
+    fn rewrite(&self, stone: usize, out: &mut Vec<usize>);
+}
+
+impl<F: Fn(usize, &mut Vec<usize>)> Rule for F {
+    fn rewrite(&self, stone: usize, out: &mut Vec<usize>) {
+        self(stone, out)
+    }
+}
+
+/// 0 becomes 1, even digit counts split in two, anything else times 2024
+pub struct Day11;
+
+/// Largest starting stone that `Day11` can blink forever without
+/// overflowing. A stone with an odd number of digits d grows to at most
+/// d + 7 digits before it splits, so 12 digits stay within 19.
+pub const MAX_STONE: usize = 999_999_999_999;
+impl Rule for Day11 {
+    fn rewrite(&self, stone: usize, out: &mut Vec<usize>) {
+        if stone == 0 {
+            out.push(1);
+            return;
+        }
+        let ndigits = digit_count(stone);
+        if ndigits.is_multiple_of(2) {
+            let (left, right) = split_digits(stone, ndigits);
+            out.push(left);
+            out.push(right);
+        } else {
+            out.push(stone.checked_mul(2024).expect("stone too large"));
+        }
+    }
+}
+
+pub struct Blinker {
+    rule: Box<dyn Rule>,
+    counts: HashMap<usize, u128>,
+    blinks: usize,
+    /// Set once a count no longer fits
+    overflowed: bool,
+    seen: HashSet<usize>,
+    /// Distinct values on the stones after each blink, from blink 0
+    distinct: Vec<usize>,
+    stable_since: Option<usize>,
+}
+
+impl Blinker {
+    pub fn new(rule: Box<dyn Rule>, stones: &[usize]) -> Blinker {
+        let mut counts = HashMap::new();
+        for &stone in stones {
+            *counts.entry(stone).or_default() += 1;
+        }
+        let seen = counts.keys().copied().collect();
+        let distinct = vec![counts.len()];
+        Blinker {
+            rule,
+            counts,
+            blinks: 0,
+            overflowed: false,
+            seen,
+            distinct,
+            stable_since: None,
+        }
+    }
+    pub fn blink(&mut self) {
+        let mut next: HashMap<usize, u128> = HashMap::with_capacity(self.counts.len());
+        let mut out = Vec::new();
+        for (&stone, &count) in &self.counts {
+            out.clear();
+            self.rule.rewrite(stone, &mut out);
+            for &new in &out {
+                let total = next.entry(new).or_default();
+                *total = total.checked_add(count).unwrap_or_else(|| {
+                    self.overflowed = true;
+                    u128::MAX
+                });
+            }
+        }
+        self.counts = next;
+        self.blinks += 1;
+        let seen_before = self.seen.len();
+        self.seen.extend(self.counts.keys().copied());
+        if self.seen.len() == seen_before {
+            // each blink's values come from the last blink's, so once
+            // nothing new turns up nothing new ever will
+            self.stable_since.get_or_insert(self.blinks);
+        }
+        self.distinct.push(self.counts.len());
+    }
+    pub fn blink_n(&mut self, n: usize) {
+        (0..n).for_each(|_| self.blink());
+    }
+    pub fn blinks(&self) -> usize {
+        self.blinks
+    }
+    /// Number of stones, None if it got too large to count
+    pub fn total(&self) -> Option<u128> {
+        if self.overflowed {
+            return None;
+        }
+        self.counts
+            .values()
+            .try_fold(0u128, |acc, &count| acc.checked_add(count))
+    }
+    /// Number of stones with each value
+    pub fn counts(&self) -> &HashMap<usize, u128> {
+        &self.counts
+    }
+    /// Distinct values on the stones after each blink so far, from blink 0
+    pub fn distinct_history(&self) -> &[usize] {
+        &self.distinct
+    }
+    /// Distinct values that have been on any stone so far
+    pub fn seen(&self) -> usize {
+        self.seen.len()
+    }
+    /// First blink that gave no value that hadn't been seen before
+    pub fn stable_since(&self) -> Option<usize> {
+        self.stable_since
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn test_digit_count() {
+        assert_eq!(digit_count(1), 1);
+        assert_eq!(digit_count(9), 1);
+        assert_eq!(digit_count(10), 2);
+        assert_eq!(split_digits(2024, 4), (20, 24));
+    }
+    #[test]
+    fn test_totals() {
+        // 0
+        // 1
+        // 2024
+        // 20 24
+        // 2 0 2 4
+        // 4048 1 4048 8096
+        // 40 48 2024 40 48 80 96
+        // 4 0 4 8 20 24 4 0 4 8 8 0 9 6
+        let mut blinker = Blinker::new(Box::new(Day11), &[0]);
+        let mut totals = vec![blinker.total().unwrap()];
+        for _ in 0..7 {
+            blinker.blink();
+            totals.push(blinker.total().unwrap());
+        }
+        assert_eq!(totals, [1, 1, 1, 2, 4, 4, 7, 14]);
+        assert_eq!(blinker.distinct_history(), [1, 1, 1, 2, 3, 3, 5, 7]);
+        assert_eq!(blinker.counts()[&4], 4);
+    }
+    #[test]
+    fn test_example() {
+        let mut blinker = Blinker::new(Box::new(Day11), &[125, 17]);
+        blinker.blink_n(6);
+        assert_eq!(blinker.total(), Some(22));
+        blinker.blink_n(19);
+        assert_eq!(blinker.total(), Some(55312));
+    }
+    #[test]
+    fn test_total_overflow() {
+        // the sum passes u128::MAX a few blinks before any single count does
+        let mut blinker = Blinker::new(Box::new(Day11), &[125, 17]);
+        blinker.blink_n(212);
+        assert_eq!(blinker.total(), None);
+        assert!(!blinker.overflowed);
+    }
+    #[test]
+    fn test_stable() {
+        let mut blinker = Blinker::new(Box::new(Day11), &[0]);
+        blinker.blink_n(200);
+        let stable = blinker.stable_since().unwrap();
+        let seen = blinker.seen();
+        assert!(stable < 200);
+        blinker.blink_n(100);
+        assert_eq!(blinker.seen(), seen);
+        // far too many stones to count by now
+        blinker.blink_n(700);
+        assert_eq!(blinker.total(), None);
+    }
+    #[test]
+    fn test_custom_rule() {
+        // halve even stones, odd stones become two copies of one less
+        let rule = |stone: usize, out: &mut Vec<usize>| {
+            if stone.is_multiple_of(2) {
+                out.push(stone / 2);
+            } else {
+                out.extend([stone - 1, stone - 1]);
+            }
+        };
+        let mut blinker = Blinker::new(Box::new(rule), &[5]);
+        blinker.blink_n(3);
+        // 5 -> 4 4 -> 2 2 -> 1 1
+        assert_eq!(blinker.total(), Some(2));
+        assert_eq!(blinker.stable_since(), None);
+        blinker.blink_n(2);
+        // 1 1 -> 0 0 0 0 -> 0 0 0 0
+        assert_eq!(blinker.total(), Some(4));
+        assert_eq!(blinker.stable_since(), Some(5));
+    }
+    #[test]
+    fn test_max_stone() {
+        // the worst case, 11 digits that grow by 4 then 3
+        let mut blinker = Blinker::new(Box::new(Day11), &[99_999_999_999, MAX_STONE]);
+        blinker.blink_n(100);
+        assert!(blinker.counts().keys().all(|&s| digit_count(s) <= 19));
+    }
+    #[test]
+    fn test_random() {
+        // keep every stone in a list and compare against the counts
+        let mut rng = Rng::new(11);
+        for _ in 0..20 {
+            let line = generate::day11(&mut rng, 4, 10000);
+            let mut stones = line
+                .split_whitespace()
+                .map(|s| s.parse().unwrap())
+                .collect::<Vec<usize>>();
+            let mut blinker = Blinker::new(Box::new(Day11), &stones);
+            for _ in 0..15 {
+                let mut next = Vec::new();
+                stones.iter().for_each(|&s| Day11.rewrite(s, &mut next));
+                stones = next;
+                blinker.blink();
+                assert_eq!(blinker.total(), Some(stones.len() as u128));
+            }
+            let mut counts = HashMap::new();
+            stones
+                .iter()
+                .for_each(|&s| *counts.entry(s).or_default() += 1);
+            assert_eq!(blinker.counts(), &counts);
+        }
+    }
+}
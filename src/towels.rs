@@ -0,0 +1,256 @@
+//! Towel arrangements for day 19
+//!
+//! The towel patterns go in a trie, so from each position of a design one
+//! walk down the trie finds every towel that fits there.
+
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    White,
+    Blue,
+    Black,
+    Red,
+    Green,
+}
+
+impl TryFrom<char> for Token {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'w' => Ok(Token::White),
+            'u' => Ok(Token::Blue),
+            'b' => Ok(Token::Black),
+            'r' => Ok(Token::Red),
+            'g' => Ok(Token::Green),
+            _ => Err(()),
+        }
+    }
+}
+
+/// None if there's a character that isn't a colour
+pub fn parse_tokens(s: &str) -> Option<Vec<Token>> {
+    s.chars().map(|c| Token::try_from(c).ok()).collect()
+}
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    children: [Option<usize>; 5],
+    /// Index of the pattern ending here
+    pattern: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct Towels {
+    patterns: Vec<Vec<Token>>,
+    nodes: Vec<Node>,
+}
+
+impl FromStr for Towels {
+    type Err = ();
+
+    /// Comma separated patterns such as `r, wr, b`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let patterns = s
+            .split(", ")
+            .map(parse_tokens)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(())?;
+        if patterns.iter().any(|p| p.is_empty()) {
+            return Err(());
+        }
+        Ok(Towels::new(patterns))
+    }
+}
+
+/// Fewest towels for the first part of a design, and the start and
+/// pattern of the last one
+type Fewest = Option<(usize, usize, usize)>;
+
+impl Towels {
+    pub fn new(patterns: Vec<Vec<Token>>) -> Towels {
+        let mut nodes = vec![Node::default()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut node = 0;
+            for &token in pattern {
+                node = match nodes[node].children[token as usize] {
+                    Some(child) => child,
+                    None => {
+                        nodes.push(Node::default());
+                        nodes[node].children[token as usize] = Some(nodes.len() - 1);
+                        nodes.len() - 1
+                    }
+                };
+            }
+            // keep the first of any repeated patterns
+            nodes[node].pattern.get_or_insert(index);
+        }
+        Towels { patterns, nodes }
+    }
+    pub fn patterns(&self) -> &[Vec<Token>] {
+        &self.patterns
+    }
+    /// Every pattern that matches `design` from `start`, as `(end, pattern)`
+    fn matches<'a>(
+        &'a self,
+        design: &'a [Token],
+        start: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = Some(0);
+        design[start..]
+            .iter()
+            .enumerate()
+            .map_while(move |(i, &token)| {
+                node = self.nodes[node?].children[token as usize];
+                Some((start + i + 1, self.nodes[node?].pattern))
+            })
+            .filter_map(|(end, pattern)| Some((end, pattern?)))
+    }
+    /// Fewest towels to make each prefix of the design, by its length
+    fn fewest(&self, design: &[Token]) -> Vec<Fewest> {
+        let mut prefixes = vec![None; design.len() + 1];
+        prefixes[0] = Some((0, 0, 0));
+        for start in 0..design.len() {
+            let Some((towels, _, _)) = prefixes[start] else {
+                continue;
+            };
+            for (end, pattern) in self.matches(design, start) {
+                let next = &mut prefixes[end];
+                if next.is_none_or(|(best, _, _)| towels + 1 < best) {
+                    *next = Some((towels + 1, start, pattern));
+                }
+            }
+        }
+        prefixes
+    }
+    pub fn possible(&self, design: &[Token]) -> bool {
+        self.min_towels(design).is_some()
+    }
+    /// Number of different ways to make the design, None if it doesn't fit
+    pub fn count_arrangements(&self, design: &[Token]) -> Option<usize> {
+        let mut counts = vec![Some(0usize); design.len() + 1];
+        counts[0] = Some(1);
+        for start in 0..design.len() {
+            let count = counts[start];
+            if count == Some(0) {
+                continue;
+            }
+            for (end, _) in self.matches(design, start) {
+                counts[end] = counts[end]
+                    .zip(count)
+                    .and_then(|(total, count)| total.checked_add(count));
+            }
+        }
+        counts[design.len()]
+    }
+    pub fn min_towels(&self, design: &[Token]) -> Option<usize> {
+        let (towels, _, _) = self.fewest(design)[design.len()]?;
+        Some(towels)
+    }
+    /// Pattern indices of an arrangement with as few towels as possible
+    pub fn arrangement(&self, design: &[Token]) -> Option<Vec<usize>> {
+        let prefixes = self.fewest(design);
+        let mut out = Vec::new();
+        let mut end = design.len();
+        while end > 0 {
+            let (_, start, pattern) = prefixes[end]?;
+            out.push(pattern);
+            end = start;
+        }
+        out.reverse();
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+
+    fn example() -> (Towels, Vec<Vec<Token>>) {
+        let (towels, designs) = include_str!("bin/19_test.txt").split_once("\n\n").unwrap();
+        let designs = designs.lines().map(|d| parse_tokens(d).unwrap()).collect();
+        (towels.parse().unwrap(), designs)
+    }
+    #[test]
+    fn test_counts() {
+        let (towels, designs) = example();
+        let counts = designs
+            .iter()
+            .map(|d| towels.count_arrangements(d).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+        let possible = designs.iter().filter(|d| towels.possible(d)).count();
+        assert_eq!(possible, 6);
+    }
+    #[test]
+    fn test_arrangement() {
+        let (towels, designs) = example();
+        // gbbr is fewest as gb, br
+        assert_eq!(towels.min_towels(&designs[2]), Some(2));
+        let arrangement = towels.arrangement(&designs[2]).unwrap();
+        let joined = arrangement
+            .iter()
+            .flat_map(|&p| towels.patterns()[p].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(joined, designs[2]);
+        assert_eq!(arrangement.len(), 2);
+        assert_eq!(towels.arrangement(&designs[4]), None);
+        assert_eq!(towels.arrangement(&[]), Some(vec![]));
+    }
+    #[test]
+    fn test_overflow() {
+        // r and rr make a run of r in a Fibonacci number of ways
+        let towels = "r, rr".parse::<Towels>().unwrap();
+        let design = parse_tokens(&"r".repeat(100)).unwrap();
+        assert!(towels.possible(&design));
+        assert_eq!(towels.min_towels(&design), Some(50));
+        assert_eq!(towels.count_arrangements(&design), None);
+        let design = parse_tokens(&"r".repeat(90)).unwrap();
+        assert_eq!(
+            towels.count_arrangements(&design),
+            Some(4660046610375530309)
+        );
+    }
+    #[test]
+    fn test_parse() {
+        assert!("r, wx".parse::<Towels>().is_err());
+        assert!("r, , b".parse::<Towels>().is_err());
+    }
+    #[test]
+    fn test_random() {
+        let mut rng = Rng::new(19);
+        for _ in 0..20 {
+            let input = generate::day19(&mut rng, 12, 20);
+            let (patterns, designs) = input.split_once("\n\n").unwrap();
+            let towels = patterns.parse::<Towels>().unwrap();
+            let patterns = patterns.split(", ").collect::<Vec<_>>();
+            for design in designs.lines() {
+                // count by trying every pattern at every position
+                let mut ways = vec![0; design.len() + 1];
+                ways[0] = 1;
+                for end in 1..=design.len() {
+                    ways[end] = patterns
+                        .iter()
+                        .filter(|p| design[..end].ends_with(*p))
+                        .map(|p| ways[end - p.len()])
+                        .sum();
+                }
+                let tokens = parse_tokens(design).unwrap();
+                let count = towels.count_arrangements(&tokens).unwrap();
+                assert_eq!(count, ways[design.len()], "{design}");
+                assert_eq!(towels.possible(&tokens), count > 0);
+                assert_eq!(towels.min_towels(&tokens).is_some(), count > 0);
+                if let Some(arrangement) = towels.arrangement(&tokens) {
+                    assert_eq!(Some(arrangement.len()), towels.min_towels(&tokens));
+                    let joined = arrangement
+                        .iter()
+                        .flat_map(|&p| towels.patterns()[p].clone())
+                        .collect::<Vec<_>>();
+                    assert_eq!(joined, tokens);
+                }
+            }
+        }
+    }
+}
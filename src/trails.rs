@@ -0,0 +1,170 @@
+//! Hiking trail analysis for day 10
+//!
+//! Trails only ever climb, so cells are visited from the highest down and
+//! each cell combines what its uphill neighbours already know. One pass
+//! gives every cell its reachable peaks and its number of distinct trails.
+
+use ndarray::prelude::*;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rules {
+    /// Height of a trailhead
+    pub start: usize,
+    /// Height of a peak
+    pub end: usize,
+    /// Whether a trail may step from the first height to the second
+    /// Must only allow climbing, `to > from`
+    pub step: fn(usize, usize) -> bool,
+}
+
+impl Rules {
+    /// Start at 0, end at 9, climb exactly 1 per step
+    pub fn day10() -> Rules {
+        Rules {
+            start: 0,
+            end: 9,
+            step: |from, to| to == from + 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Analysis {
+    rules: Rules,
+    heights: Array2<usize>,
+    peaks: Vec<[usize; 2]>,
+    /// Bitset over `peaks` for each cell
+    reachable: Array2<Vec<u64>>,
+    trails: Array2<usize>,
+}
+
+fn neighbours(position: [usize; 2], shape: &[usize]) -> impl Iterator<Item = [usize; 2]> {
+    let [row, col] = position;
+    [
+        (row + 1 < shape[0]).then(|| [row + 1, col]),
+        (row > 0).then(|| [row - 1, col]),
+        (col + 1 < shape[1]).then(|| [row, col + 1]),
+        (col > 0).then(|| [row, col - 1]),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+pub fn analyze(heights: ArrayView2<usize>, rules: Rules) -> Analysis {
+    let peaks = heights
+        .indexed_iter()
+        .filter(|(_, &h)| h == rules.end)
+        .map(|((row, col), _)| [row, col])
+        .collect::<Vec<_>>();
+    let words = peaks.len().div_ceil(64);
+    let mut reachable = Array2::from_elem(heights.raw_dim(), vec![0_u64; words]);
+    let mut trails = Array2::zeros(heights.raw_dim());
+    for (i, peak) in peaks.iter().enumerate() {
+        reachable[*peak][i / 64] |= 1 << (i % 64);
+        trails[*peak] = 1;
+    }
+    let mut order = heights
+        .indexed_iter()
+        .filter(|(_, &h)| h < rules.end)
+        .map(|((row, col), &h)| (h, [row, col]))
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| b.cmp(a));
+    let shape = heights.shape();
+    for (height, position) in order {
+        let mut peaks_here = vec![0_u64; words];
+        let mut trails_here = 0;
+        for next in neighbours(position, shape) {
+            if !(rules.step)(height, heights[next]) {
+                continue;
+            }
+            assert!(heights[next] > height, "trails must climb");
+            for (a, b) in peaks_here.iter_mut().zip(&reachable[next]) {
+                *a |= b;
+            }
+            trails_here += trails[next];
+        }
+        reachable[position] = peaks_here;
+        trails[position] = trails_here;
+    }
+    Analysis {
+        rules,
+        heights: heights.to_owned(),
+        peaks,
+        reachable,
+        trails,
+    }
+}
+
+impl Analysis {
+    pub fn trailheads(&self) -> Vec<[usize; 2]> {
+        self.heights
+            .indexed_iter()
+            .filter(|(_, &h)| h == self.rules.start)
+            .map(|((row, col), _)| [row, col])
+            .collect()
+    }
+    /// Number of peaks reachable from `position`
+    pub fn score(&self, position: [usize; 2]) -> usize {
+        self.reachable[position]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+    /// Number of distinct trails from `position` to any peak
+    pub fn rating(&self, position: [usize; 2]) -> usize {
+        self.trails[position]
+    }
+    pub fn reachable_peaks(&self, position: [usize; 2]) -> Vec<[usize; 2]> {
+        let bits = &self.reachable[position];
+        (0..self.peaks.len())
+            .filter(|i| bits[i / 64] & (1 << (i % 64)) != 0)
+            .map(|i| self.peaks[i])
+            .collect()
+    }
+    pub fn total_score(&self) -> usize {
+        self.trailheads().into_iter().map(|p| self.score(p)).sum()
+    }
+    pub fn total_rating(&self) -> usize {
+        self.trailheads().into_iter().map(|p| self.rating(p)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid;
+
+    fn parse(s: &str) -> Array2<usize> {
+        grid::parse(s, |_, c| c.to_digit(10).map(|d| d as usize)).unwrap()
+    }
+    #[test]
+    fn test_example() {
+        let heights = parse(include_str!("bin/10_test.txt"));
+        let analysis = analyze(heights.view(), Rules::day10());
+        assert_eq!(analysis.trailheads().len(), 9);
+        assert_eq!(analysis.total_score(), 36);
+        assert_eq!(analysis.total_rating(), 81);
+        assert_eq!(analysis.score([0, 2]), 5);
+        assert_eq!(analysis.rating([0, 2]), 20);
+    }
+    #[test]
+    fn test_small() {
+        let heights = parse("0123\n1234\n8765\n9876");
+        let analysis = analyze(heights.view(), Rules::day10());
+        assert_eq!(analysis.reachable_peaks([0, 0]), [[3, 0]]);
+        assert_eq!(analysis.rating([0, 0]), 16);
+    }
+    #[test]
+    fn test_rules() {
+        // climb by 1 or 2 from 1 up to 5
+        let rules = Rules {
+            start: 1,
+            end: 5,
+            step: |from, to| to > from && to - from <= 2,
+        };
+        let heights = parse("135\n234");
+        let analysis = analyze(heights.view(), rules);
+        assert_eq!(analysis.total_score(), 1);
+        assert_eq!(analysis.rating([0, 0]), 2);
+    }
+}
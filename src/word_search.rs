@@ -0,0 +1,276 @@
+//! Find words and 2D patterns in a grid of characters (day 4)
+
+use crate::grid;
+use ndarray::prelude::*;
+use std::str::FromStr;
+
+/// All eight directions a word can be read in, as `[row, col]` steps
+pub const DIRECTIONS: [[i64; 2]; 8] = [
+    [-1, 0],
+    [1, 0],
+    [0, -1],
+    [0, 1],
+    [-1, 1],
+    [-1, -1],
+    [1, 1],
+    [1, -1],
+];
+
+/// None if the lines aren't all the same length
+pub fn parse_grid(s: &str) -> Option<Array2<char>> {
+    grid::parse(s, |_, c| Some(c))
+}
+
+/// Check every `(offset, char)` of `cells` relative to `position`
+fn matches_at(grid: ArrayView2<char>, position: [usize; 2], cells: &[([i64; 2], char)]) -> bool {
+    cells.iter().all(|(offset, c)| {
+        let row = usize::try_from(position[0] as i64 + offset[0]);
+        let col = usize::try_from(position[1] as i64 + offset[1]);
+        match (row, col) {
+            (Ok(row), Ok(col)) => grid.get([row, col]) == Some(c),
+            _ => false,
+        }
+    })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WordMatch {
+    /// Index into the searched words
+    pub word: usize,
+    /// Position of the first letter
+    pub position: [usize; 2],
+    pub direction: [i64; 2],
+}
+
+/// Directions to try so each placement of a word is found once
+/// A palindrome reads the same both ways along a line, so only one of each
+/// opposite pair is used, and a single letter has just the one placement
+fn word_directions(word: &[char]) -> &'static [[i64; 2]] {
+    if word.len() == 1 {
+        &[[0, 1]]
+    } else if word.iter().eq(word.iter().rev()) {
+        &[[1, 0], [0, 1], [1, 1], [1, -1]]
+    } else {
+        &DIRECTIONS
+    }
+}
+
+/// Find every word reading in a straight line in any of the eight directions
+pub fn find_words(grid: ArrayView2<char>, words: &[&str]) -> Vec<WordMatch> {
+    let mut out = Vec::new();
+    for (word_index, word) in words.iter().enumerate() {
+        let word = word.chars().collect::<Vec<_>>();
+        let Some(&first) = word.first() else {
+            continue;
+        };
+        let patterns = word_directions(&word)
+            .iter()
+            .map(|&direction| {
+                let cells = word
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &c)| ([direction[0] * k as i64, direction[1] * k as i64], c))
+                    .collect::<Vec<_>>();
+                (direction, cells)
+            })
+            .collect::<Vec<_>>();
+        for ((i, j), &c) in grid.indexed_iter() {
+            if c != first {
+                continue;
+            }
+            for (direction, cells) in &patterns {
+                if matches_at(grid, [i, j], cells) {
+                    out.push(WordMatch {
+                        word: word_index,
+                        position: [i, j],
+                        direction: *direction,
+                    });
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Reflect left-right first, then turn clockwise by quarter turns
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Orientation {
+    pub reflected: bool,
+    pub quarter_turns: u8,
+}
+
+/// 2D pattern where `.` matches any character
+/// Parsed from lines of text, the top left character is the origin
+#[derive(Debug, Clone)]
+pub struct Stencil {
+    cells: Vec<([i64; 2], char)>,
+}
+
+impl FromStr for Stencil {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        for (irow, line) in s.lines().enumerate() {
+            for (icol, c) in line.chars().enumerate() {
+                if c != '.' {
+                    cells.push(([irow as i64, icol as i64], c));
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err(());
+        }
+        Ok(Stencil { cells })
+    }
+}
+
+impl Stencil {
+    pub fn transform(&self, orientation: Orientation) -> Stencil {
+        let cells = self
+            .cells
+            .iter()
+            .map(|&([row, col], c)| {
+                let mut offset = if orientation.reflected {
+                    [row, -col]
+                } else {
+                    [row, col]
+                };
+                for _ in 0..orientation.quarter_turns {
+                    offset = [offset[1], -offset[0]];
+                }
+                (offset, c)
+            })
+            .collect();
+        Stencil { cells }
+    }
+    /// Cells shifted so the bounding box starts at zero, for comparing shapes
+    fn normalized(&self) -> Vec<([i64; 2], char)> {
+        let min_row = self.cells.iter().map(|(o, _)| o[0]).min().unwrap();
+        let min_col = self.cells.iter().map(|(o, _)| o[1]).min().unwrap();
+        let mut cells = self
+            .cells
+            .iter()
+            .map(|&(o, c)| ([o[0] - min_row, o[1] - min_col], c))
+            .collect::<Vec<_>>();
+        cells.sort();
+        cells
+    }
+    /// Every rotation and reflection that gives a different pattern
+    pub fn orientations(&self) -> Vec<(Orientation, Stencil)> {
+        let mut out: Vec<(Orientation, Stencil)> = Vec::with_capacity(8);
+        for reflected in [false, true] {
+            for quarter_turns in 0..4 {
+                let orientation = Orientation {
+                    reflected,
+                    quarter_turns,
+                };
+                let stencil = self.transform(orientation);
+                let normalized = stencil.normalized();
+                if out.iter().all(|(_, s)| s.normalized() != normalized) {
+                    out.push((orientation, stencil));
+                }
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StencilMatch {
+    /// Index into the searched stencils
+    pub stencil: usize,
+    /// Where the stencil origin landed
+    pub position: [usize; 2],
+    pub orientation: Orientation,
+}
+
+/// Find every placement of any stencil in any of its distinct orientations
+pub fn find_stencils(grid: ArrayView2<char>, stencils: &[Stencil]) -> Vec<StencilMatch> {
+    let mut out = Vec::new();
+    for (stencil_index, stencil) in stencils.iter().enumerate() {
+        for (orientation, oriented) in stencil.orientations() {
+            for ((i, j), _) in grid.indexed_iter() {
+                if matches_at(grid, [i, j], &oriented.cells) {
+                    out.push(StencilMatch {
+                        stencil: stencil_index,
+                        position: [i, j],
+                        orientation,
+                    });
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzz;
+
+    #[test]
+    fn test_find_words() {
+        let grid = parse_grid("XMAS\nMM..\nA.A.\nS..S").unwrap();
+        let out = find_words(grid.view(), &["XMAS", "SAM"]);
+        let positions = out
+            .iter()
+            .map(|m| (m.word, m.position, m.direction))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                (0, [0, 0], [1, 0]),
+                (0, [0, 0], [0, 1]),
+                (0, [0, 0], [1, 1]),
+                (1, [0, 3], [0, -1]),
+                (1, [3, 0], [-1, 0]),
+                (1, [3, 3], [-1, -1]),
+            ]
+        );
+    }
+    #[test]
+    fn test_palindromes() {
+        let grid = parse_grid("A").unwrap();
+        assert_eq!(find_words(grid.view(), &["A"]).len(), 1);
+        let grid = parse_grid("ABA").unwrap();
+        let out = find_words(grid.view(), &["ABA", "B", "AB"]);
+        let positions = out
+            .iter()
+            .map(|m| (m.word, m.position, m.direction))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                (0, [0, 0], [0, 1]),
+                (1, [0, 1], [0, 1]),
+                (2, [0, 0], [0, 1]),
+                (2, [0, 2], [0, -1]),
+            ]
+        );
+        let grid = parse_grid("ABA\nB.B\nABA").unwrap();
+        assert_eq!(find_words(grid.view(), &["ABA"]).len(), 4);
+        assert_eq!(find_words(grid.view(), &["AB"]).len(), 8);
+    }
+    #[test]
+    fn test_orientations() {
+        let cross = "M.S\n.A.\nM.S".parse::<Stencil>().unwrap();
+        assert_eq!(cross.orientations().len(), 4);
+        let ell = "AB\nC.".parse::<Stencil>().unwrap();
+        assert_eq!(ell.orientations().len(), 8);
+        let block = "A".parse::<Stencil>().unwrap();
+        assert_eq!(block.orientations().len(), 1);
+    }
+    #[test]
+    fn test_fuzz() {
+        let cross = "M.S\n.A.\nM.S".parse::<Stencil>().unwrap();
+        fuzz::fuzz(0, &[include_str!("bin/04_test.txt")], 300, |s| {
+            if let Some(grid) = parse_grid(s) {
+                find_words(grid.view(), &["XMAS"]);
+                find_stencils(grid.view(), std::slice::from_ref(&cross));
+            }
+        });
+        assert!(parse_grid("XMAS\nXM").is_none());
+        assert!(parse_grid("").is_none());
+    }
+}